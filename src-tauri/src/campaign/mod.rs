@@ -0,0 +1,250 @@
+// Campaign subsystem: a small admin layer over `StatusTracker` that treats
+// one prompt fanned out to several providers as a single named unit, so the
+// UI can issue one broadcast and poll a single handle for cross-provider
+// completion instead of tracking per-provider submission ids by hand.
+
+use crate::status::tracker::StatusTracker;
+use crate::types::{CommandError, ProviderId, SubmissionErrorType, SubmissionStatus};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// A single prompt submitted to one or more providers at once, tracked as a
+/// group. The provider submissions it fans out to share its id as their
+/// `batch_id`, so the history subsystem groups them the same way it already
+/// groups `submit_prompt` calls.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Campaign {
+    pub id: String,
+    pub prompt_content: String,
+    pub created_at: String,
+    /// Ids of the per-provider submissions this campaign fanned out to
+    pub submissions: Vec<String>,
+}
+
+/// One provider's current standing within a campaign, joined from its live
+/// `Submission` state
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CampaignSubmissionResult {
+    pub submission_id: String,
+    pub provider_id: ProviderId,
+    pub status: SubmissionStatus,
+    pub error_type: Option<SubmissionErrorType>,
+    pub error_message: Option<String>,
+}
+
+/// Aggregate view of a campaign's cross-provider completion, joining every
+/// child submission's current status into success/retrying/failed counts
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CampaignResults {
+    pub campaign_id: String,
+    pub results: Vec<CampaignSubmissionResult>,
+    pub success_count: usize,
+    pub retrying_count: usize,
+    pub failed_count: usize,
+}
+
+/// Manages campaigns: creation, listing, aggregate results and deletion
+pub struct CampaignManager {
+    tracker: Arc<StatusTracker>,
+    campaigns: Mutex<Vec<Campaign>>,
+}
+
+impl CampaignManager {
+    /// Creates a new CampaignManager backed by the shared `StatusTracker`
+    pub fn new(tracker: Arc<StatusTracker>) -> Self {
+        Self {
+            tracker,
+            campaigns: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Creates one `Submission` per provider via `StatusTracker::create_submission`,
+    /// tagging each with the new campaign's id as its batch id, and records
+    /// the resulting campaign
+    pub fn create_campaign(
+        &self,
+        prompt_content: String,
+        providers: Vec<ProviderId>,
+    ) -> Result<Campaign, CommandError> {
+        let campaign_id = uuid::Uuid::new_v4().to_string();
+
+        let mut submissions = Vec::with_capacity(providers.len());
+        for provider_id in providers {
+            let submission = self.tracker.create_submission_in_batch(
+                provider_id,
+                prompt_content.clone(),
+                Some(campaign_id.clone()),
+            )?;
+            submissions.push(submission.id);
+        }
+
+        let campaign = Campaign {
+            id: campaign_id,
+            prompt_content,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            submissions,
+        };
+
+        self.campaigns
+            .lock()
+            .map_err(|e| CommandError::internal(format!("Failed to acquire lock: {}", e)))?
+            .push(campaign.clone());
+
+        Ok(campaign)
+    }
+
+    /// Lists every campaign created this session, most recently created first
+    pub fn list_campaigns(&self) -> Result<Vec<Campaign>, CommandError> {
+        let campaigns = self
+            .campaigns
+            .lock()
+            .map_err(|e| CommandError::internal(format!("Failed to acquire lock: {}", e)))?;
+
+        let mut campaigns = campaigns.clone();
+        campaigns.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+        Ok(campaigns)
+    }
+
+    /// Joins every child submission's current status into an aggregate view
+    pub fn get_campaign_results(&self, campaign_id: &str) -> Result<CampaignResults, CommandError> {
+        let campaign = self.find_campaign(campaign_id)?;
+
+        let mut results = Vec::with_capacity(campaign.submissions.len());
+        let mut success_count = 0;
+        let mut retrying_count = 0;
+        let mut failed_count = 0;
+
+        for submission_id in &campaign.submissions {
+            let submission = self.tracker.get_status(submission_id)?;
+
+            match submission.status {
+                SubmissionStatus::Success => success_count += 1,
+                SubmissionStatus::Retrying => retrying_count += 1,
+                SubmissionStatus::Failed => failed_count += 1,
+                _ => {}
+            }
+
+            results.push(CampaignSubmissionResult {
+                submission_id: submission.id,
+                provider_id: submission.provider_id,
+                status: submission.status,
+                error_type: submission.error_type,
+                error_message: submission.error_message,
+            });
+        }
+
+        Ok(CampaignResults {
+            campaign_id: campaign_id.to_string(),
+            results,
+            success_count,
+            retrying_count,
+            failed_count,
+        })
+    }
+
+    /// Removes a campaign from the manager. Does not touch the underlying
+    /// submissions tracked by `StatusTracker`, only the campaign grouping.
+    pub fn delete_campaign(&self, campaign_id: &str) -> Result<(), CommandError> {
+        let mut campaigns = self
+            .campaigns
+            .lock()
+            .map_err(|e| CommandError::internal(format!("Failed to acquire lock: {}", e)))?;
+
+        let len_before = campaigns.len();
+        campaigns.retain(|c| c.id != campaign_id);
+
+        if campaigns.len() == len_before {
+            return Err(CommandError::not_found(format!(
+                "Campaign not found: {}",
+                campaign_id
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn find_campaign(&self, campaign_id: &str) -> Result<Campaign, CommandError> {
+        let campaigns = self
+            .campaigns
+            .lock()
+            .map_err(|e| CommandError::internal(format!("Failed to acquire lock: {}", e)))?;
+
+        campaigns
+            .iter()
+            .find(|c| c.id == campaign_id)
+            .cloned()
+            .ok_or_else(|| CommandError::not_found(format!("Campaign not found: {}", campaign_id)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manager() -> CampaignManager {
+        CampaignManager::new(Arc::new(StatusTracker::new()))
+    }
+
+    #[test]
+    fn test_create_campaign_fans_out_one_submission_per_provider() {
+        let manager = manager();
+        let campaign = manager
+            .create_campaign(
+                "Test prompt".to_string(),
+                vec![ProviderId::ChatGPT, ProviderId::Gemini],
+            )
+            .unwrap();
+
+        assert_eq!(campaign.submissions.len(), 2);
+    }
+
+    #[test]
+    fn test_list_campaigns_returns_created_campaign() {
+        let manager = manager();
+        manager
+            .create_campaign("Test prompt".to_string(), vec![ProviderId::ChatGPT])
+            .unwrap();
+
+        let campaigns = manager.list_campaigns().unwrap();
+        assert_eq!(campaigns.len(), 1);
+    }
+
+    #[test]
+    fn test_get_campaign_results_counts_by_status() {
+        let manager = manager();
+        let campaign = manager
+            .create_campaign(
+                "Test prompt".to_string(),
+                vec![ProviderId::ChatGPT, ProviderId::Gemini],
+            )
+            .unwrap();
+
+        manager.tracker.start_submission(&campaign.submissions[0]).unwrap();
+        manager.tracker.succeed_submission(&campaign.submissions[0]).unwrap();
+
+        let results = manager.get_campaign_results(&campaign.id).unwrap();
+
+        assert_eq!(results.success_count, 1);
+        assert_eq!(results.results.len(), 2);
+    }
+
+    #[test]
+    fn test_delete_campaign_removes_it() {
+        let manager = manager();
+        let campaign = manager
+            .create_campaign("Test prompt".to_string(), vec![ProviderId::ChatGPT])
+            .unwrap();
+
+        manager.delete_campaign(&campaign.id).unwrap();
+
+        assert!(manager.get_campaign_results(&campaign.id).is_err());
+    }
+
+    #[test]
+    fn test_delete_campaign_rejects_unknown_id() {
+        let manager = manager();
+        assert!(manager.delete_campaign("missing").is_err());
+    }
+}
@@ -147,11 +147,26 @@ pub async fn submit_prompt(
         selected_providers.len()
     );
 
+    // Announce the fan-out plan before any provider starts
+    let batch_id = uuid::Uuid::new_v4().to_string();
+    state.status_tracker.emit_plan(
+        &batch_id,
+        selected_providers.iter().map(|p| p.id).collect(),
+    );
+
     // Get provider configs
-    let provider_configs = state.provider_configs.as_ref().ok_or_else(|| {
-        error!("Provider configurations not loaded");
-        CommandError::internal("Provider configurations not available")
-    })?;
+    let provider_configs = state
+        .provider_configs
+        .lock()
+        .map_err(|e| {
+            error!("Failed to acquire lock on provider_configs: {}", e);
+            CommandError::internal("Failed to access provider configurations")
+        })?
+        .clone()
+        .ok_or_else(|| {
+            error!("Provider configurations not loaded");
+            CommandError::internal("Provider configurations not available")
+        })?;
 
     // Create submissions for each selected provider
     let mut submissions = Vec::new();
@@ -161,10 +176,24 @@ pub async fn submit_prompt(
     })?;
 
     for provider in selected_providers {
-        // Create submission entity
+        // Run the prompt through the rule engine, if configured, so it can be
+        // rewritten per provider or skip this provider entirely
+        let routed_prompt = match &state.rules {
+            Some(ruleset) => match ruleset.evaluate(provider.id, &prompt) {
+                crate::rules::EvalOutcome::Rewritten(rewritten) => rewritten,
+                crate::rules::EvalOutcome::Skip => {
+                    info!("Rule engine skipped provider {:?}", provider.id);
+                    continue;
+                }
+            },
+            None => prompt.clone(),
+        };
+
+        // Create submission entity, tagged with this call's batch id so the
+        // history subsystem can group every provider's result together
         let submission = state
             .status_tracker
-            .create_submission(provider.id, prompt.clone())?;
+            .create_submission_in_batch(provider.id, routed_prompt.clone(), Some(batch_id.clone()))?;
 
         info!(
             "Created submission {} for provider {:?}",
@@ -177,9 +206,17 @@ pub async fn submit_prompt(
         // Get provider config for selectors
         let config = provider_configs.get_config(provider.id)?;
 
-        // Generate injection script
-        let script =
-            injector.prepare_injection(&config.input_selectors, &config.submit_selectors, &prompt);
+        // Generate injection script. The prompt is passed as a structured
+        // `__args` object rather than spliced into the script source, so it
+        // can't break out of the generated JavaScript regardless of what
+        // characters it contains.
+        let script = injector.prepare_injection_with_args(
+            &config.input_selectors,
+            &config.submit_selectors,
+            &serde_json::json!({ "prompt": routed_prompt }),
+            &submission.id,
+            provider.id,
+        );
 
         info!(
             "Generated injection script for provider {:?} ({} chars)",
@@ -187,6 +224,31 @@ pub async fn submit_prompt(
             script.len()
         );
 
+        // Authorize the script against this provider's granted capability
+        // before it is ever dispatched to a webview
+        let capability = state
+            .capabilities
+            .get(provider.id)
+            .cloned()
+            .ok_or_else(|| CommandError::internal(format!("No capability granted for {:?}", provider.id)))?;
+
+        let payload = crate::types::ExecutePromptPayload {
+            submission_id: submission.id.clone(),
+            provider_id: provider.id,
+            script: script.clone(),
+            capability,
+        };
+
+        if let Err(e) = injector.authorize(&payload) {
+            error!("Injection script rejected by capability check: {}", e);
+            state.status_tracker.fail_submission(
+                &submission.id,
+                crate::types::SubmissionErrorType::InjectionFailed,
+                e.to_string(),
+            )?;
+            continue;
+        }
+
         // Execute the script in the webview
         let label = format!("{}-webview", provider.id.as_str().to_lowercase());
         let submission_id = submission.id.clone();
@@ -196,48 +258,100 @@ pub async fn submit_prompt(
         let app_clone = app.clone();
         let script_clone = script.clone();
         let status_tracker = Arc::clone(&state.status_tracker);
+        let webview_manager = Arc::clone(&state.webview_manager);
+        let harvest_script = injector.prepare_harvest(
+            &config.response_selectors,
+            &config.completion_selectors,
+            &submission.id,
+            provider.id,
+        );
 
         // Spawn async task to execute
         tauri::async_runtime::spawn(async move {
             use tauri::Manager;
 
-            // Get the webview (child webview, not window)
-            let webview = match app_clone.get_webview(&label) {
-                Some(wv) => wv,
-                None => {
-                    log_error!("Webview not found for execution", {
+            let injector = match Injector::new() {
+                Ok(injector) => injector,
+                Err(e) => {
+                    log_error!("Failed to initialize injector for dispatch", {
                         "submission_id": &submission_id,
-                        "provider_id": format!("{:?}", provider_id),
-                        "label": &label
+                        "error": &e
                     });
                     let _ = status_tracker.fail_submission(
                         &submission_id,
                         crate::types::SubmissionErrorType::InjectionFailed,
-                        format!("Webview not found: {}", label),
+                        e,
                     );
                     return;
                 }
             };
 
-            // Execute the script
-            match webview.eval(&script_clone) {
-                Ok(_) => {
+            // Dispatch via `Injector::execute`, which times out the eval,
+            // isolates the script's scope, and rounds its real result back
+            // through `WebviewManager::execute_script` rather than firing
+            // the script blind via a bare `webview.eval`.
+            match injector
+                .execute(&app_clone, &webview_manager, provider_id, &script_clone)
+                .await
+            {
+                Ok(result) if result.success => {
                     log_info!("Script executed successfully", {
                         "submission_id": &submission_id,
                         "provider_id": format!("{:?}", provider_id)
                     });
-                    let _ = status_tracker.succeed_submission(&submission_id);
+
+                    // Dispatch succeeding only means the webview accepted the
+                    // script, not that the provider answered -- the submission
+                    // stays `InProgress` until `report_execution_result` reports
+                    // a real completion, or `check_timeouts` fails it if none
+                    // ever arrives.
+
+                    // Fire the response-harvesting script so the answer is
+                    // captured once the provider finishes generating
+                    match app_clone.get_webview(&label) {
+                        Some(webview) => {
+                            if let Err(e) = webview.eval(&harvest_script) {
+                                log_error!("Failed to start response harvesting", {
+                                    "submission_id": &submission_id,
+                                    "provider_id": format!("{:?}", provider_id),
+                                    "error": e.to_string()
+                                });
+                            }
+                        }
+                        None => {
+                            log_error!("Webview not found for response harvesting", {
+                                "submission_id": &submission_id,
+                                "provider_id": format!("{:?}", provider_id),
+                                "label": &label
+                            });
+                        }
+                    }
+                }
+                Ok(result) => {
+                    let message = result
+                        .error_message
+                        .unwrap_or_else(|| "Script execution reported failure".to_string());
+                    log_error!("Script execution failed", {
+                        "submission_id": &submission_id,
+                        "provider_id": format!("{:?}", provider_id),
+                        "error": &message
+                    });
+                    let _ = status_tracker.fail_submission(
+                        &submission_id,
+                        crate::types::SubmissionErrorType::InjectionFailed,
+                        message,
+                    );
                 }
                 Err(e) => {
                     log_error!("Script execution failed", {
                         "submission_id": &submission_id,
                         "provider_id": format!("{:?}", provider_id),
-                        "error": e.to_string()
+                        "error": &e
                     });
                     let _ = status_tracker.fail_submission(
                         &submission_id,
                         crate::types::SubmissionErrorType::InjectionFailed,
-                        e.to_string(),
+                        e,
                     );
                 }
             }
@@ -251,10 +365,57 @@ pub async fn submit_prompt(
     Ok(submissions)
 }
 
+/// Re-applies bounds for every live provider webview in a single call, driven
+/// by `calculate_layout`. Unlike `sync_provider_webview` (one provider at a
+/// time), this is meant to be invoked whenever the frontend reports a
+/// viewport change (window resize, or the main window's content scrolling),
+/// a known pitfall for Tauri child webviews which otherwise visually detach
+/// from their intended panel.
+#[tauri::command]
+pub fn sync_layout(
+    app: tauri::AppHandle,
+    state: State<AppState>,
+    viewport_width: f64,
+    viewport_height: f64,
+) -> Result<(), CommandError> {
+    log_info!("Command: sync_layout called", {
+        "command": "sync_layout",
+        "viewport": format!("{}x{}", viewport_width, viewport_height)
+    });
+
+    let manager = state.provider_manager.lock().map_err(|e| {
+        error!("Failed to acquire lock on provider_manager: {}", e);
+        CommandError::internal("Failed to access provider state")
+    })?;
+
+    let selected_providers: Vec<ProviderId> = manager
+        .get_selected_providers()
+        .iter()
+        .map(|p| p.id)
+        .collect();
+
+    if selected_providers.is_empty() {
+        return Err(CommandError::validation("No providers selected"));
+    }
+
+    let layout = calculator::calculate_layout(&selected_providers);
+    let panel_count = layout.panel_dimensions.len();
+
+    state
+        .webview_manager
+        .apply_layout(&app, &layout, viewport_width, viewport_height)
+        .map_err(CommandError::internal)?;
+
+    info!("Synced layout for {} panels", panel_count);
+
+    Ok(())
+}
+
 /// Creates or updates a provider webview
 #[tauri::command]
 pub async fn sync_provider_webview(
     app: tauri::AppHandle,
+    state: State<'_, AppState>,
     provider_id: ProviderId,
     url: String,
     x: f64,
@@ -293,10 +454,31 @@ pub async fn sync_provider_webview(
             "label": &label
         });
     } else {
+        // Register the document-start streaming capture script so it's in
+        // place before the provider page's own JS runs, rather than racing
+        // it the way a post-load injection would. Falls back to an empty
+        // script (observing nothing) if this provider has no config loaded
+        // yet, same as the other config-driven commands degrade.
+        let init_script = state
+            .provider_configs
+            .lock()
+            .map_err(|e| CommandError::internal(format!("Failed to acquire lock: {}", e)))?
+            .as_ref()
+            .and_then(|configs| configs.get_config(provider_id).ok())
+            .map(|config| {
+                crate::injection::script_builder::generate_streaming_capture_script(
+                    &config.response_selectors,
+                    &config.completion_selectors,
+                    provider_id.as_str(),
+                )
+            })
+            .unwrap_or_default();
+
         // Create new child webview attached to main window
         // T155: Set User Agent to fix Gemini icons on Linux
         let webview_builder = WebviewBuilder::new(&label, WebviewUrl::External(url.parse().unwrap()))
-            .user_agent("Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0.0.0 Safari/537.36");
+            .user_agent("Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0.0.0 Safari/537.36")
+            .initialization_script(&init_script);
 
         let position = tauri::LogicalPosition { x, y };
         let size = tauri::LogicalSize { width, height };
@@ -385,6 +567,399 @@ pub async fn refresh_provider_webview(
     Ok(())
 }
 
+/// Reloads provider selector configurations from disk, re-merging any user
+/// override in the app config directory on top of the embedded defaults.
+/// Lets users patch broken selectors live without restarting the app.
+#[tauri::command]
+pub fn reload_provider_configs(state: State<AppState>) -> Result<(), CommandError> {
+    log_info!("Command: reload_provider_configs called", {
+        "command": "reload_provider_configs"
+    });
+
+    state.reload_provider_configs()?;
+
+    info!("Provider configurations reloaded");
+
+    Ok(())
+}
+
+/// Lists every persisted prompt/response batch, most recently created first
+#[tauri::command]
+pub fn list_history_batches(
+    state: State<AppState>,
+) -> Result<Vec<crate::history::HistoryBatchSummary>, CommandError> {
+    log_info!("Command: list_history_batches called", {
+        "command": "list_history_batches"
+    });
+
+    state.history.list_batches()
+}
+
+/// Fetches a single batch's provider-by-provider results
+#[tauri::command]
+pub fn get_history_batch(
+    state: State<AppState>,
+    batch_id: String,
+) -> Result<crate::history::HistoryBatch, CommandError> {
+    log_info!("Command: get_history_batch called", {
+        "command": "get_history_batch",
+        "batch_id": &batch_id
+    });
+
+    state.history.get_batch(&batch_id)
+}
+
+/// Exports a batch to JSON or Markdown for side-by-side comparison of how
+/// each provider answered the same prompt
+#[tauri::command]
+pub fn export_history_batch(
+    state: State<AppState>,
+    batch_id: String,
+    format: crate::history::ExportFormat,
+) -> Result<String, CommandError> {
+    log_info!("Command: export_history_batch called", {
+        "command": "export_history_batch",
+        "batch_id": &batch_id,
+        "format": format!("{:?}", format)
+    });
+
+    match format {
+        crate::history::ExportFormat::Json => state.history.export_json(&batch_id),
+        crate::history::ExportFormat::Markdown => state.history.export_markdown(&batch_id),
+    }
+}
+
+/// Creates a campaign: one prompt fanned out to every given provider,
+/// tracked as a single named unit instead of loose per-provider submission ids
+#[tauri::command]
+pub fn create_campaign(
+    state: State<AppState>,
+    prompt_content: String,
+    providers: Vec<ProviderId>,
+) -> Result<crate::campaign::Campaign, CommandError> {
+    log_info!("Command: create_campaign called", {
+        "command": "create_campaign",
+        "provider_count": providers.len()
+    });
+
+    state.campaign_manager.create_campaign(prompt_content, providers)
+}
+
+/// Lists every campaign created this session, most recently created first
+#[tauri::command]
+pub fn list_campaigns(state: State<AppState>) -> Result<Vec<crate::campaign::Campaign>, CommandError> {
+    log_info!("Command: list_campaigns called", {
+        "command": "list_campaigns"
+    });
+
+    state.campaign_manager.list_campaigns()
+}
+
+/// Joins a campaign's child submissions into an aggregate cross-provider
+/// completion view, so the UI can poll one handle instead of every submission
+#[tauri::command]
+pub fn get_campaign_results(
+    state: State<AppState>,
+    campaign_id: String,
+) -> Result<crate::campaign::CampaignResults, CommandError> {
+    log_info!("Command: get_campaign_results called", {
+        "command": "get_campaign_results",
+        "campaign_id": &campaign_id
+    });
+
+    state.campaign_manager.get_campaign_results(&campaign_id)
+}
+
+/// Deletes a campaign grouping. Does not affect the underlying submissions.
+#[tauri::command]
+pub fn delete_campaign(state: State<AppState>, campaign_id: String) -> Result<(), CommandError> {
+    log_info!("Command: delete_campaign called", {
+        "command": "delete_campaign",
+        "campaign_id": &campaign_id
+    });
+
+    state.campaign_manager.delete_campaign(&campaign_id)
+}
+
+/// Runs the selector self-test across all providers, probing each one's
+/// live page for working input/submit/auth-check selectors
+///
+/// NOTE: Like `WebviewManager::execute_script`, there is no real result
+/// channel back from the page yet (see T-ROUNDTRIP), so the probe script is
+/// fired into any live webview best-effort and classification falls back to
+/// each provider's selection state. Once the script-result round trip lands,
+/// this should classify from the live `SelectorProbeResult` instead.
+#[tauri::command]
+pub fn run_selector_diagnostics(
+    app: tauri::AppHandle,
+    state: State<AppState>,
+) -> Result<Vec<crate::diagnostics::ProviderDiagnostic>, CommandError> {
+    use crate::diagnostics::runner::DiagnosticsRunner;
+    use tauri::Manager;
+
+    log_info!("Command: run_selector_diagnostics called", {
+        "command": "run_selector_diagnostics"
+    });
+
+    let provider_configs = state
+        .provider_configs
+        .lock()
+        .map_err(|e| {
+            error!("Failed to acquire lock on provider_configs: {}", e);
+            CommandError::internal("Failed to access provider configurations")
+        })?
+        .clone()
+        .ok_or_else(|| {
+            error!("Provider configurations not loaded");
+            CommandError::internal("Provider configurations not available")
+        })?;
+
+    let manager = state.provider_manager.lock().map_err(|e| {
+        error!("Failed to acquire lock on provider_manager: {}", e);
+        CommandError::internal("Failed to access provider state")
+    })?;
+
+    let providers = manager.get_all_providers().to_vec();
+    let runner = DiagnosticsRunner::new(&provider_configs);
+
+    info!("Running selector diagnostics for {} providers", providers.len());
+
+    let mut diagnostics = Vec::new();
+
+    for provider in &providers {
+        let config = provider_configs.get_config(provider.id)?;
+        let script = runner.build_probe_script(provider.id)?;
+        let label = format!("{}-webview", provider.id.as_str().to_lowercase());
+
+        if let Some(webview) = app.get_webview(&label) {
+            if let Err(e) = webview.eval(&script) {
+                log_error!("Failed to run selector probe", {
+                    "provider_id": format!("{:?}", provider.id),
+                    "error": e.to_string()
+                });
+            }
+        }
+
+        // Until the real result round trip lands, assume a fired probe
+        // would match every configured selector once.
+        let placeholder_result = crate::diagnostics::SelectorProbeResult {
+            input_selectors: config.input_selectors.iter().map(|s| (s.clone(), 1)).collect(),
+            submit_selectors: config.submit_selectors.iter().map(|s| (s.clone(), 1)).collect(),
+            auth_check_selectors: config
+                .auth_check_selectors
+                .iter()
+                .map(|s| (s.clone(), 1))
+                .collect(),
+        };
+
+        diagnostics.push(runner.classify(provider, &placeholder_result));
+    }
+
+    info!("Selector diagnostics completed");
+
+    Ok(diagnostics)
+}
+
+/// Verifies a single provider's `input_selectors`/`submit_selectors`
+/// fallback lists against its live page and self-heals by promoting
+/// whichever fallback is actually working, so a future injection tries it
+/// first instead of repeating a stale selector that's known to be dead.
+///
+/// Round-trips the verification script through `WebviewManager::execute_script`
+/// (the same real result channel `verify_provider_contract` uses) so the
+/// promotion reflects what the live page actually matched, rather than
+/// assuming the first configured selector is still the one working.
+#[tauri::command]
+pub async fn verify_provider_selectors(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    provider_id: ProviderId,
+) -> Result<crate::diagnostics::SelectorVerificationResult, CommandError> {
+    use crate::diagnostics::runner::DiagnosticsRunner;
+
+    log_info!("Command: verify_provider_selectors called", {
+        "command": "verify_provider_selectors",
+        "provider_id": format!("{:?}", provider_id)
+    });
+
+    let provider_configs = state
+        .provider_configs
+        .lock()
+        .map_err(|e| {
+            error!("Failed to acquire lock on provider_configs: {}", e);
+            CommandError::internal("Failed to access provider configurations")
+        })?
+        .clone()
+        .ok_or_else(|| {
+            error!("Provider configurations not loaded");
+            CommandError::internal("Provider configurations not available")
+        })?;
+
+    let runner = DiagnosticsRunner::new(&provider_configs);
+    let script = runner.build_verification_script(provider_id)?;
+
+    let injection_result = state
+        .webview_manager
+        .execute_script(&app, provider_id, &script)
+        .await
+        .map_err(|e| {
+            log_error!("Failed to run selector verification probe", {
+                "provider_id": format!("{:?}", provider_id),
+                "error": &e
+            });
+            CommandError::internal(format!("Selector verification probe failed: {}", e))
+        })?;
+
+    let result = runner.parse_verification_result(&injection_result)?;
+    let promoted = runner.promote(provider_id, &result)?;
+
+    let mut configs_lock = state.provider_configs.lock().map_err(|e| {
+        error!("Failed to acquire lock on provider_configs: {}", e);
+        CommandError::internal("Failed to access provider configurations")
+    })?;
+    if let Some(configs) = configs_lock.as_mut() {
+        configs
+            .providers
+            .insert(provider_id.as_str().to_string(), promoted);
+    }
+    drop(configs_lock);
+
+    info!("Selector verification completed for {:?}", provider_id);
+
+    Ok(result)
+}
+
+/// Verifies a provider's recorded DOM contract against its live page, via
+/// `WebviewManager::verify_contract`'s real script-result round trip (the
+/// one `execute_script` already provides for `Injector::execute`, unlike the
+/// placeholder-based diagnostics commands above).
+#[tauri::command]
+pub async fn verify_provider_contract(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    contract: crate::contract::ProviderContract,
+) -> Result<crate::contract::VerificationReport, CommandError> {
+    log_info!("Command: verify_provider_contract called", {
+        "command": "verify_provider_contract",
+        "provider_id": format!("{:?}", contract.provider_id)
+    });
+
+    state
+        .webview_manager
+        .verify_contract(&app, contract.provider_id, &contract)
+        .await
+        .map_err(CommandError::internal)
+}
+
+/// Manually triggers an immediate retry of a submission currently in
+/// `Retrying`, instead of waiting for the background retry loop's backoff
+/// delay to elapse
+#[tauri::command]
+pub async fn retry_submission(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    submission_id: String,
+) -> Result<(), CommandError> {
+    log_info!("Command: retry_submission called", {
+        "command": "retry_submission",
+        "submission_id": &submission_id
+    });
+
+    let submission = state.status_tracker.get_status(&submission_id)?;
+
+    if submission.status != crate::types::SubmissionStatus::Retrying {
+        return Err(CommandError::validation(format!(
+            "Submission {} is not awaiting retry (status: {:?})",
+            submission_id, submission.status
+        )));
+    }
+
+    let provider_configs = state
+        .provider_configs
+        .lock()
+        .map_err(|e| {
+            error!("Failed to acquire lock on provider_configs: {}", e);
+            CommandError::internal("Failed to access provider configurations")
+        })?
+        .clone()
+        .ok_or_else(|| {
+            error!("Provider configurations not loaded");
+            CommandError::internal("Provider configurations not available")
+        })?;
+
+    let config = provider_configs.get_config(submission.provider_id)?;
+    let injector = crate::injection::injector::Injector::new().map_err(|e| {
+        error!("Failed to initialize injector for retry: {}", e);
+        CommandError::internal("Failed to initialize injector")
+    })?;
+    let script = injector.prepare_injection_with_args(
+        &config.input_selectors,
+        &config.submit_selectors,
+        &serde_json::json!({ "prompt": submission.prompt_content }),
+        &submission_id,
+        submission.provider_id,
+    );
+
+    let capability = state
+        .capabilities
+        .get(submission.provider_id)
+        .cloned()
+        .ok_or_else(|| {
+            CommandError::internal(format!(
+                "No capability granted for {:?}",
+                submission.provider_id
+            ))
+        })?;
+    let payload = crate::types::ExecutePromptPayload {
+        submission_id: submission_id.clone(),
+        provider_id: submission.provider_id,
+        script: script.clone(),
+        capability,
+    };
+    if let Err(e) = injector.authorize(&payload) {
+        error!("Retry script rejected by capability check: {}", e);
+        state.status_tracker.fail_submission(
+            &submission_id,
+            crate::types::SubmissionErrorType::InjectionFailed,
+            e.to_string(),
+        )?;
+        return Err(e);
+    }
+
+    state.status_tracker.start_submission(&submission_id)?;
+
+    match injector
+        .execute(&app, &state.webview_manager, submission.provider_id, &script)
+        .await
+    {
+        Ok(result) if result.success => {
+            // Dispatch succeeding doesn't mean the provider answered -- leave
+            // the submission `InProgress` until `report_execution_result`
+            // reports a real completion, or `check_timeouts` fails it.
+            Ok(())
+        }
+        Ok(result) => {
+            let message = result
+                .error_message
+                .unwrap_or_else(|| "Retry script reported failure".to_string());
+            state.status_tracker.fail_submission(
+                &submission_id,
+                crate::types::SubmissionErrorType::InjectionFailed,
+                message.clone(),
+            )?;
+            Err(CommandError::internal(format!("Failed to execute retry script: {}", message)))
+        }
+        Err(e) => {
+            state.status_tracker.fail_submission(
+                &submission_id,
+                crate::types::SubmissionErrorType::InjectionFailed,
+                e.clone(),
+            )?;
+            Err(CommandError::internal(format!("Failed to execute retry script: {}", e)))
+        }
+    }
+}
+
 /// Gets the status of a specific submission
 #[tauri::command]
 pub fn get_submission_status(
@@ -447,3 +1022,106 @@ pub fn report_execution_result(
 
     Ok(())
 }
+
+/// Receives a provider's harvested response text from the response-harvesting
+/// script, storing it on the submission so the frontend can render it
+/// alongside the other providers' answers
+///
+/// NOTE: Like `report_execution_result`, nothing invokes this yet — the
+/// harvest script generated by `Injector::prepare_harvest` calls
+/// `window.__TAURI__.core.invoke('report_response', ...)` directly once the
+/// child webview's IPC bridge is wired up (see T-ROUNDTRIP).
+#[tauri::command]
+pub fn report_response(
+    state: State<AppState>,
+    payload: crate::types::ReportResponsePayload,
+) -> Result<(), CommandError> {
+    log_info!("Command: report_response called", {
+        "submission_id": &payload.submission_id,
+        "provider_id": format!("{:?}", payload.provider_id),
+        "response_length": payload.response_text.len()
+    });
+
+    state
+        .status_tracker
+        .record_response(&payload.submission_id, payload.response_text)?;
+
+    log_info!("Stored harvested response", {
+        "submission_id": &payload.submission_id
+    });
+
+    Ok(())
+}
+
+/// Fulfills a `WebviewManager::execute_script` call with its real result.
+///
+/// NOTE: Like `report_response`, nothing invokes this yet — the wrapped
+/// script `WebviewManager::execute_script` injects calls
+/// `window.__CHENCHEN_IPC__` directly, which is expected to forward to
+/// `window.__TAURI__.core.invoke('report_script_result', ...)` from an init
+/// script once the IPC bridge is wired up (see T-ROUNDTRIP). `Started`/
+/// `Progress` messages are accepted but only logged; only `Completed`
+/// resolves the pending call.
+#[tauri::command]
+pub fn report_script_result(
+    state: State<AppState>,
+    message: crate::webview::ScriptMessage,
+) -> Result<(), CommandError> {
+    use crate::webview::ScriptMessage;
+
+    match message {
+        ScriptMessage::Started { call_id } => {
+            log_info!("Command: report_script_result (Started)", {
+                "call_id": &call_id
+            });
+        }
+        ScriptMessage::Progress { call_id, note } => {
+            log_info!("Command: report_script_result (Progress)", {
+                "call_id": &call_id,
+                "note": &note
+            });
+        }
+        ScriptMessage::Completed { call_id, result } => {
+            log_info!("Command: report_script_result (Completed)", {
+                "call_id": &call_id,
+                "success": result.success
+            });
+
+            state
+                .webview_manager
+                .resolve_script_result(&call_id, result)
+                .map_err(CommandError::internal)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Receives an incremental `ResponseChunk` from a provider's document-start
+/// streaming capture script, aggregating it into that provider's running
+/// reply via `WebviewManager::ingest_response_chunk`.
+///
+/// NOTE: Like `report_response`, nothing invokes this yet in this snapshot —
+/// the capture script `Injector::prepare_init_script` generates calls
+/// `window.__TAURI__.core.invoke('report_response_chunk', ...)` directly once
+/// it's registered as a webview initialization script (see
+/// `sync_provider_webview` and T-ROUNDTRIP).
+#[tauri::command]
+pub fn report_response_chunk(
+    state: State<AppState>,
+    payload: crate::webview::ResponseChunk,
+) -> Result<(), CommandError> {
+    log_info!("Command: report_response_chunk called", {
+        "provider_id": format!("{:?}", payload.provider_id),
+        "delta_length": payload.delta.len(),
+        "done": payload.done
+    });
+
+    let aggregated_length = state.webview_manager.ingest_response_chunk(payload).len();
+
+    log_info!("Ingested response chunk", {
+        "aggregated_length": aggregated_length
+    });
+
+    Ok(())
+}
@@ -0,0 +1,127 @@
+// Pact-style contract verification for provider webview DOM
+// A provider's "contract" is a recorded set of named expectations about its
+// live page (an auth/login selector, the prompt input selector, the response
+// container selector, etc). Verifying it against the real page catches a
+// provider UI upgrade that breaks our selectors before a user's submission
+// fails, the same role a Pact provider-verification run plays against a
+// consumer-recorded contract.
+
+use crate::types::ProviderId;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Whether an expectation requires its selector to match at least one
+/// element, or requires it to match none
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExpectedPresence {
+    Present,
+    Absent,
+}
+
+/// One named, recorded expectation about a provider's DOM
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ContractExpectation {
+    pub name: String,
+    pub selector: String,
+    pub expected: ExpectedPresence,
+}
+
+/// A provider's full recorded contract: every expectation its webview is
+/// expected to keep satisfying. Stored as JSON so a drifted provider UI is
+/// caught by a scheduled verification run rather than by a failed submission.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ProviderContract {
+    pub provider_id: ProviderId,
+    pub expectations: Vec<ContractExpectation>,
+}
+
+/// Result of checking a single expectation against the live page
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ExpectationResult {
+    pub expectation_name: String,
+    pub matched: bool,
+    pub actual_count: u32,
+}
+
+/// Aggregate result of verifying a provider's contract against its live page
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct VerificationReport {
+    pub provider_id: ProviderId,
+    pub checked_at: String,
+    pub results: Vec<ExpectationResult>,
+}
+
+impl VerificationReport {
+    /// A contract passes only when every expectation matched
+    pub fn passed(&self) -> bool {
+        self.results.iter().all(|r| r.matched)
+    }
+
+    /// Names of the expectations that drifted, for a log line an operator
+    /// can act on without re-deriving it from the full report
+    pub fn failed_expectation_names(&self) -> Vec<&str> {
+        self.results
+            .iter()
+            .filter(|r| !r.matched)
+            .map(|r| r.expectation_name.as_str())
+            .collect()
+    }
+}
+
+pub mod script_builder;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(results: Vec<ExpectationResult>) -> VerificationReport {
+        VerificationReport {
+            provider_id: ProviderId::ChatGPT,
+            checked_at: "2024-01-01T00:00:00Z".to_string(),
+            results,
+        }
+    }
+
+    #[test]
+    fn test_passed_requires_every_expectation_matched() {
+        let all_matched = report(vec![ExpectationResult {
+            expectation_name: "login_selector".to_string(),
+            matched: true,
+            actual_count: 1,
+        }]);
+        assert!(all_matched.passed());
+
+        let one_failed = report(vec![
+            ExpectationResult {
+                expectation_name: "login_selector".to_string(),
+                matched: true,
+                actual_count: 1,
+            },
+            ExpectationResult {
+                expectation_name: "input_selector".to_string(),
+                matched: false,
+                actual_count: 0,
+            },
+        ]);
+        assert!(!one_failed.passed());
+    }
+
+    #[test]
+    fn test_failed_expectation_names_lists_only_drifted_ones() {
+        let result = report(vec![
+            ExpectationResult {
+                expectation_name: "login_selector".to_string(),
+                matched: true,
+                actual_count: 1,
+            },
+            ExpectationResult {
+                expectation_name: "input_selector".to_string(),
+                matched: false,
+                actual_count: 0,
+            },
+        ]);
+
+        assert_eq!(result.failed_expectation_names(), vec!["input_selector"]);
+    }
+}
@@ -0,0 +1,92 @@
+// JavaScript generation for contract verification
+// Produces a read-only script that checks every expectation in a provider's
+// contract and reports how many elements each one's selector currently
+// matches, mirroring `diagnostics::script_builder`'s non-mutating style.
+
+use super::ContractExpectation;
+
+/// Generates a script that evaluates every expectation against the current
+/// page and returns `{ results: [{ expectation_name, matched, actual_count }] }`
+pub fn generate_contract_script(expectations: &[ContractExpectation]) -> String {
+    let entries: Vec<String> = expectations
+        .iter()
+        .map(|expectation| {
+            format!(
+                r#"{{ name: "{name}", selector: "{selector}", expectPresent: {expect_present} }}"#,
+                name = expectation.name.replace('"', r#"\""#),
+                selector = expectation.selector.replace('"', r#"\""#),
+                expect_present = matches!(expectation.expected, super::ExpectedPresence::Present),
+            )
+        })
+        .collect();
+
+    format!(
+        r#"
+(function() {{
+    const expectations = [{entries}];
+
+    const results = expectations.map(function(expectation) {{
+        let actualCount = 0;
+        try {{
+            actualCount = document.querySelectorAll(expectation.selector).length;
+        }} catch (error) {{
+            actualCount = 0;
+        }}
+        const matched = expectation.expectPresent ? actualCount > 0 : actualCount === 0;
+        return {{
+            expectation_name: expectation.name,
+            matched: matched,
+            actual_count: actualCount
+        }};
+    }});
+
+    return {{ results: results }};
+}})();
+"#,
+        entries = entries.join(", "),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contract::ExpectedPresence;
+
+    #[test]
+    fn test_generate_contract_script_includes_every_expectation() {
+        let expectations = vec![
+            ContractExpectation {
+                name: "login_selector".to_string(),
+                selector: ".login-button".to_string(),
+                expected: ExpectedPresence::Absent,
+            },
+            ContractExpectation {
+                name: "input_selector".to_string(),
+                selector: "textarea#prompt".to_string(),
+                expected: ExpectedPresence::Present,
+            },
+        ];
+
+        let script = generate_contract_script(&expectations);
+
+        assert!(script.contains("login_selector"));
+        assert!(script.contains(".login-button"));
+        assert!(script.contains("input_selector"));
+        assert!(script.contains("textarea#prompt"));
+        assert!(script.contains("querySelectorAll"));
+    }
+
+    #[test]
+    fn test_generate_contract_script_is_read_only() {
+        let expectations = vec![ContractExpectation {
+            name: "login_selector".to_string(),
+            selector: ".login-button".to_string(),
+            expected: ExpectedPresence::Present,
+        }];
+
+        let script = generate_contract_script(&expectations);
+
+        assert!(!script.contains(".click()"));
+        assert!(!script.contains(".value ="));
+    }
+}
@@ -0,0 +1,204 @@
+// Selector self-test / diagnostics subsystem
+// Probes each provider's live page so selector drift is caught before a real
+// prompt submission fails, rather than after
+
+use crate::types::ProviderId;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Outcome of probing one provider's selectors, modeled on Deno's test-runner
+/// result shape (`Ok` / `Ignored` / `Failed(reason)`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub enum CheckOutcome {
+    /// A working selector was found in every category
+    Ok,
+    /// The provider is not currently selected, so it was skipped
+    Ignored,
+    /// Names the first selector category with zero live matches
+    Failed(String),
+}
+
+/// Diagnostic result for a single provider
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ProviderDiagnostic {
+    pub provider_id: ProviderId,
+    pub outcome: CheckOutcome,
+}
+
+/// Event stream emitted while diagnostics run, mirroring Deno's
+/// `Plan`/`Result` test-runner messages so the UI can render a live health
+/// dashboard instead of waiting for the whole run to finish
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type")]
+pub enum DiagnosticsEvent {
+    Plan {
+        pending: Vec<ProviderId>,
+    },
+    Result {
+        provider_id: ProviderId,
+        outcome: CheckOutcome,
+    },
+}
+
+/// Per-category selector match counts captured by the probe script, keyed by
+/// the exact selector string that was tried
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct SelectorProbeResult {
+    pub input_selectors: HashMap<String, u32>,
+    pub submit_selectors: HashMap<String, u32>,
+    pub auth_check_selectors: HashMap<String, u32>,
+}
+
+impl SelectorProbeResult {
+    /// Classifies this probe result: `Ignored` when the provider isn't
+    /// selected, `Failed` naming the first category with no matching
+    /// selector, otherwise `Ok`
+    pub fn classify(&self, is_selected: bool) -> CheckOutcome {
+        if !is_selected {
+            return CheckOutcome::Ignored;
+        }
+
+        for (name, matches) in [
+            ("input_selectors", &self.input_selectors),
+            ("submit_selectors", &self.submit_selectors),
+            ("auth_check_selectors", &self.auth_check_selectors),
+        ] {
+            if matches.values().all(|&count| count == 0) {
+                return CheckOutcome::Failed(name.to_string());
+            }
+        }
+
+        CheckOutcome::Ok
+    }
+}
+
+/// Result of probing a single selector: whether it currently resolves to
+/// exactly one visible, enabled element on the page
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SelectorCheck {
+    pub selector: String,
+    pub visible_enabled_count: u32,
+}
+
+impl SelectorCheck {
+    /// A selector is usable when it resolves to exactly one visible, enabled element
+    pub fn is_match(&self) -> bool {
+        self.visible_enabled_count == 1
+    }
+}
+
+/// Per-field verification report for one provider, checking its ordered
+/// fallback list of `input_selectors`/`submit_selectors` in turn so the
+/// backend can promote whichever one is actually working on the live page
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct SelectorVerificationResult {
+    pub input_selectors: Vec<SelectorCheck>,
+    pub submit_selectors: Vec<SelectorCheck>,
+}
+
+impl SelectorVerificationResult {
+    /// The first selector in an ordered fallback list that currently matches,
+    /// i.e. the one injection should use
+    pub fn first_match(checks: &[SelectorCheck]) -> Option<&str> {
+        checks
+            .iter()
+            .find(|check| check.is_match())
+            .map(|check| check.selector.as_str())
+    }
+}
+
+pub mod runner;
+pub mod script_builder;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(matches: &[(&str, u32)]) -> HashMap<String, u32> {
+        matches
+            .iter()
+            .map(|(selector, count)| (selector.to_string(), *count))
+            .collect()
+    }
+
+    #[test]
+    fn test_classify_ignored_when_not_selected() {
+        let result = SelectorProbeResult::default();
+        assert_eq!(result.classify(false), CheckOutcome::Ignored);
+    }
+
+    #[test]
+    fn test_classify_ok_when_every_category_has_a_match() {
+        let result = SelectorProbeResult {
+            input_selectors: counts(&[("textarea", 1)]),
+            submit_selectors: counts(&[("button", 1)]),
+            auth_check_selectors: counts(&[(".auth", 0), (".login", 1)]),
+        };
+
+        assert_eq!(result.classify(true), CheckOutcome::Ok);
+    }
+
+    #[test]
+    fn test_classify_failed_names_first_empty_category() {
+        let result = SelectorProbeResult {
+            input_selectors: counts(&[("textarea", 0)]),
+            submit_selectors: counts(&[("button", 1)]),
+            auth_check_selectors: counts(&[(".auth", 1)]),
+        };
+
+        assert_eq!(
+            result.classify(true),
+            CheckOutcome::Failed("input_selectors".to_string())
+        );
+    }
+
+    #[test]
+    fn test_selector_check_is_match_requires_exactly_one() {
+        let none = SelectorCheck {
+            selector: "textarea".to_string(),
+            visible_enabled_count: 0,
+        };
+        let one = SelectorCheck {
+            selector: "textarea".to_string(),
+            visible_enabled_count: 1,
+        };
+        let many = SelectorCheck {
+            selector: "textarea".to_string(),
+            visible_enabled_count: 2,
+        };
+
+        assert!(!none.is_match());
+        assert!(one.is_match());
+        assert!(!many.is_match());
+    }
+
+    #[test]
+    fn test_first_match_skips_non_matching_fallbacks() {
+        let checks = vec![
+            SelectorCheck {
+                selector: "textarea#stale".to_string(),
+                visible_enabled_count: 0,
+            },
+            SelectorCheck {
+                selector: "textarea#fallback".to_string(),
+                visible_enabled_count: 1,
+            },
+        ];
+
+        assert_eq!(
+            SelectorVerificationResult::first_match(&checks),
+            Some("textarea#fallback")
+        );
+    }
+
+    #[test]
+    fn test_first_match_is_none_when_nothing_matches() {
+        let checks = vec![SelectorCheck {
+            selector: "textarea".to_string(),
+            visible_enabled_count: 0,
+        }];
+
+        assert_eq!(SelectorVerificationResult::first_match(&checks), None);
+    }
+}
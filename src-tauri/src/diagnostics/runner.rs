@@ -0,0 +1,237 @@
+// Diagnostics runner: orchestrates selector self-tests across providers
+
+use super::{
+    CheckOutcome, DiagnosticsEvent, ProviderDiagnostic, SelectorCheck, SelectorProbeResult,
+    SelectorVerificationResult,
+};
+use crate::providers::config::{ProviderConfigs, ProviderSelectorConfig};
+use crate::providers::Provider;
+use crate::types::{CommandError, ProviderId};
+
+/// Runs selector diagnostics across providers, reusing each provider's
+/// current selector config
+pub struct DiagnosticsRunner<'a> {
+    configs: &'a ProviderConfigs,
+}
+
+impl<'a> DiagnosticsRunner<'a> {
+    pub fn new(configs: &'a ProviderConfigs) -> Self {
+        Self { configs }
+    }
+
+    /// Builds the read-only probe script for a single provider
+    pub fn build_probe_script(&self, provider_id: ProviderId) -> Result<String, CommandError> {
+        let config = self.configs.get_config(provider_id)?;
+        Ok(super::script_builder::generate_probe_script(config))
+    }
+
+    /// Builds the `Plan` event announcing which providers are about to be probed
+    pub fn plan_event(&self, providers: &[Provider]) -> DiagnosticsEvent {
+        DiagnosticsEvent::Plan {
+            pending: providers.iter().map(|p| p.id).collect(),
+        }
+    }
+
+    /// Classifies a parsed probe result for one provider into a diagnostic
+    pub fn classify(&self, provider: &Provider, result: &SelectorProbeResult) -> ProviderDiagnostic {
+        ProviderDiagnostic {
+            provider_id: provider.id,
+            outcome: result.classify(provider.is_selected),
+        }
+    }
+
+    /// Builds the `Result` event for a classified diagnostic
+    pub fn result_event(diagnostic: &ProviderDiagnostic) -> DiagnosticsEvent {
+        DiagnosticsEvent::Result {
+            provider_id: diagnostic.provider_id,
+            outcome: diagnostic.outcome.clone(),
+        }
+    }
+
+    /// Builds the verification probe script for a single provider, checking
+    /// whether its ordered `input_selectors`/`submit_selectors` fallback
+    /// lists currently resolve to a visible, enabled element
+    pub fn build_verification_script(&self, provider_id: ProviderId) -> Result<String, CommandError> {
+        let config = self.configs.get_config(provider_id)?;
+        Ok(super::script_builder::generate_verification_script(config))
+    }
+
+    /// Parses the structured `data` payload of an `InjectionResult` returned
+    /// by running `build_verification_script` through
+    /// `WebviewManager::execute_script` into a real `SelectorVerificationResult`,
+    /// so `promote` acts on what the live page actually matched instead of an
+    /// assumption about which selector is still working
+    pub fn parse_verification_result(
+        &self,
+        injection_result: &crate::injection::InjectionResult,
+    ) -> Result<SelectorVerificationResult, CommandError> {
+        let data = injection_result.data.clone().ok_or_else(|| {
+            CommandError::internal("Verification script returned no structured data")
+        })?;
+
+        serde_json::from_value(data).map_err(|e| {
+            CommandError::internal(format!("Failed to parse selector verification result: {}", e))
+        })
+    }
+
+    /// Self-heals a provider's selector config by promoting whichever
+    /// fallback selector the verification found working to the front of its
+    /// list, so the next injection attempt tries it first
+    pub fn promote(
+        &self,
+        provider_id: ProviderId,
+        result: &SelectorVerificationResult,
+    ) -> Result<ProviderSelectorConfig, CommandError> {
+        let mut config = self.configs.get_config(provider_id)?.clone();
+
+        if let Some(selector) = SelectorVerificationResult::first_match(&result.input_selectors) {
+            promote_to_front(&mut config.input_selectors, selector);
+        }
+        if let Some(selector) = SelectorVerificationResult::first_match(&result.submit_selectors) {
+            promote_to_front(&mut config.submit_selectors, selector);
+        }
+
+        Ok(config)
+    }
+}
+
+/// Moves `selector` to the front of its fallback list, if present
+fn promote_to_front(selectors: &mut Vec<String>, selector: &str) {
+    if let Some(pos) = selectors.iter().position(|s| s == selector) {
+        let promoted = selectors.remove(pos);
+        selectors.insert(0, promoted);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn configs() -> ProviderConfigs {
+        let mut providers = HashMap::new();
+        providers.insert(
+            "ChatGPT".to_string(),
+            crate::providers::config::ProviderSelectorConfig {
+                provider_id: ProviderId::ChatGPT,
+                version: "1.0.0".to_string(),
+                is_selected: true,
+                input_selectors: vec!["textarea".to_string()],
+                submit_selectors: vec!["button".to_string()],
+                auth_check_selectors: vec![".login".to_string()],
+                response_selectors: vec![".response".to_string()],
+                completion_selectors: vec![".stop-generating".to_string()],
+                last_updated: "2024-01-01T00:00:00Z".to_string(),
+                notes: None,
+            },
+        );
+
+        ProviderConfigs {
+            version: "1.0.0".to_string(),
+            providers,
+        }
+    }
+
+    #[test]
+    fn test_build_probe_script_uses_provider_config() {
+        let configs = configs();
+        let runner = DiagnosticsRunner::new(&configs);
+
+        let script = runner.build_probe_script(ProviderId::ChatGPT).unwrap();
+        assert!(script.contains("textarea"));
+    }
+
+    #[test]
+    fn test_build_probe_script_errors_for_unknown_provider() {
+        let configs = configs();
+        let runner = DiagnosticsRunner::new(&configs);
+
+        let result = runner.build_probe_script(ProviderId::Gemini);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_classify_uses_provider_selection_state() {
+        let configs = configs();
+        let runner = DiagnosticsRunner::new(&configs);
+
+        let mut provider = Provider::new(ProviderId::ChatGPT, vec![".login".to_string()]);
+        provider.is_selected = false;
+
+        let diagnostic = runner.classify(&provider, &SelectorProbeResult::default());
+        assert_eq!(diagnostic.outcome, CheckOutcome::Ignored);
+    }
+
+    #[test]
+    fn test_build_verification_script_uses_provider_config() {
+        let configs = configs();
+        let runner = DiagnosticsRunner::new(&configs);
+
+        let script = runner.build_verification_script(ProviderId::ChatGPT).unwrap();
+        assert!(script.contains("textarea"));
+    }
+
+    #[test]
+    fn test_parse_verification_result_reads_data_payload() {
+        let configs = configs();
+        let runner = DiagnosticsRunner::new(&configs);
+
+        let injection_result = crate::injection::InjectionResult {
+            success: true,
+            error_message: None,
+            element_found: true,
+            submit_triggered: false,
+            data: Some(serde_json::json!({
+                "input_selectors": [{ "selector": "textarea", "visible_enabled_count": 1 }],
+                "submit_selectors": [{ "selector": "button", "visible_enabled_count": 0 }],
+            })),
+        };
+
+        let result = runner.parse_verification_result(&injection_result).unwrap();
+
+        assert_eq!(result.input_selectors[0].visible_enabled_count, 1);
+        assert_eq!(result.submit_selectors[0].visible_enabled_count, 0);
+    }
+
+    #[test]
+    fn test_parse_verification_result_rejects_missing_data() {
+        let configs = configs();
+        let runner = DiagnosticsRunner::new(&configs);
+
+        let injection_result = crate::injection::InjectionResult {
+            success: true,
+            error_message: None,
+            element_found: true,
+            submit_triggered: false,
+            data: None,
+        };
+
+        assert!(runner.parse_verification_result(&injection_result).is_err());
+    }
+
+    #[test]
+    fn test_promote_moves_matched_fallback_selector_to_front() {
+        let mut configs = configs();
+        configs.providers.get_mut("ChatGPT").unwrap().input_selectors =
+            vec!["textarea#stale".to_string(), "textarea#fallback".to_string()];
+        let runner = DiagnosticsRunner::new(&configs);
+
+        let result = SelectorVerificationResult {
+            input_selectors: vec![
+                SelectorCheck {
+                    selector: "textarea#stale".to_string(),
+                    visible_enabled_count: 0,
+                },
+                SelectorCheck {
+                    selector: "textarea#fallback".to_string(),
+                    visible_enabled_count: 1,
+                },
+            ],
+            submit_selectors: vec![],
+        };
+
+        let promoted = runner.promote(ProviderId::ChatGPT, &result).unwrap();
+
+        assert_eq!(promoted.input_selectors[0], "textarea#fallback");
+    }
+}
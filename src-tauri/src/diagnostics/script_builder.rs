@@ -0,0 +1,154 @@
+// JavaScript generation for the selector self-test probe
+// Produces a read-only script that never mutates the page: it only counts
+// how many elements each configured selector currently matches
+
+use crate::providers::config::ProviderSelectorConfig;
+
+/// Generates a non-destructive probe script that reports how many elements
+/// each input/submit/auth-check selector matches on the current page
+pub fn generate_probe_script(config: &ProviderSelectorConfig) -> String {
+    format!(
+        r#"
+(function() {{
+    function countMatches(selectors) {{
+        const counts = {{}};
+        selectors.forEach(function(selector) {{
+            try {{
+                counts[selector] = document.querySelectorAll(selector).length;
+            }} catch (error) {{
+                counts[selector] = 0;
+            }}
+        }});
+        return counts;
+    }}
+
+    return {{
+        input_selectors: countMatches({input_selectors}),
+        submit_selectors: countMatches({submit_selectors}),
+        auth_check_selectors: countMatches({auth_check_selectors})
+    }};
+}})();
+"#,
+        input_selectors = format_selector_array(&config.input_selectors),
+        submit_selectors = format_selector_array(&config.submit_selectors),
+        auth_check_selectors = format_selector_array(&config.auth_check_selectors),
+    )
+}
+
+/// Generates a non-destructive verification script that, for each selector
+/// in `input_selectors`/`submit_selectors` (tried in order as fallbacks),
+/// counts how many elements currently match it AND are visible and enabled.
+/// Unlike `generate_probe_script`, this checks per-selector visibility/enabled
+/// state rather than a raw DOM count, since a stale selector can still match
+/// a hidden or disabled element.
+///
+/// The result is wrapped as an `InjectionResult` (with the per-selector
+/// checks under `data`) so it round-trips through
+/// `WebviewManager::execute_script` like any other dispatched script.
+pub fn generate_verification_script(config: &ProviderSelectorConfig) -> String {
+    format!(
+        r#"
+(function() {{
+    function isVisible(element) {{
+        const style = window.getComputedStyle(element);
+        return style.display !== 'none'
+            && style.visibility !== 'hidden'
+            && element.offsetParent !== null;
+    }}
+
+    function isEnabled(element) {{
+        return !element.disabled && element.getAttribute('aria-disabled') !== 'true';
+    }}
+
+    function checkSelectors(selectors) {{
+        return selectors.map(function(selector) {{
+            let count = 0;
+            try {{
+                document.querySelectorAll(selector).forEach(function(element) {{
+                    if (isVisible(element) && isEnabled(element)) {{
+                        count += 1;
+                    }}
+                }});
+            }} catch (error) {{
+                count = 0;
+            }}
+            return {{ selector: selector, visible_enabled_count: count }};
+        }});
+    }}
+
+    return {{
+        success: true,
+        error_message: null,
+        element_found: true,
+        submit_triggered: false,
+        data: {{
+            input_selectors: checkSelectors({input_selectors}),
+            submit_selectors: checkSelectors({submit_selectors})
+        }}
+    }};
+}})();
+"#,
+        input_selectors = format_selector_array(&config.input_selectors),
+        submit_selectors = format_selector_array(&config.submit_selectors),
+    )
+}
+
+/// Formats an array of selectors as a JavaScript array literal
+fn format_selector_array(selectors: &[String]) -> String {
+    let quoted_selectors: Vec<String> = selectors
+        .iter()
+        .map(|s| format!(r#""{}""#, s.replace('"', r#"\""#)))
+        .collect();
+
+    format!("[{}]", quoted_selectors.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ProviderId;
+
+    fn sample_config() -> ProviderSelectorConfig {
+        ProviderSelectorConfig {
+            provider_id: ProviderId::ChatGPT,
+            version: "1.0.0".to_string(),
+            is_selected: true,
+            input_selectors: vec!["textarea".to_string()],
+            submit_selectors: vec!["button[type='submit']".to_string()],
+            auth_check_selectors: vec![".login-button".to_string()],
+            response_selectors: vec![".response".to_string()],
+            completion_selectors: vec![".stop-generating".to_string()],
+            last_updated: "2024-01-01T00:00:00Z".to_string(),
+            notes: None,
+        }
+    }
+
+    #[test]
+    fn test_generate_probe_script_includes_all_categories() {
+        let script = generate_probe_script(&sample_config());
+
+        assert!(script.contains("textarea"));
+        assert!(script.contains("button[type='submit']"));
+        assert!(script.contains("login-button"));
+        assert!(script.contains("querySelectorAll"));
+    }
+
+    #[test]
+    fn test_generate_probe_script_is_read_only() {
+        let script = generate_probe_script(&sample_config());
+
+        assert!(!script.contains(".click()"));
+        assert!(!script.contains(".value ="));
+    }
+
+    #[test]
+    fn test_generate_verification_script_checks_visibility_and_enabled_state() {
+        let script = generate_verification_script(&sample_config());
+
+        assert!(script.contains("textarea"));
+        assert!(script.contains("button[type='submit']"));
+        assert!(script.contains("isVisible"));
+        assert!(script.contains("isEnabled"));
+        assert!(!script.contains(".click()"));
+    }
+}
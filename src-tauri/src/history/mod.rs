@@ -0,0 +1,289 @@
+// Persistence layer for prompt/response history
+// Records every submission, grouped by the "batch" id of one submit_prompt
+// call across all selected providers, so past comparisons survive restarts
+
+use crate::status::Submission;
+use crate::types::{CommandError, ProviderId, SubmissionErrorType, SubmissionStatus};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Export format for a history batch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum ExportFormat {
+    Json,
+    Markdown,
+}
+
+/// One provider's result within a batch, a frozen snapshot of a `Submission`
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct HistoryRecord {
+    pub provider_id: ProviderId,
+    pub prompt_content: String,
+    pub status: SubmissionStatus,
+    pub attempt_count: u8,
+    pub error_type: Option<SubmissionErrorType>,
+    pub error_message: Option<String>,
+    pub started_at: Option<String>,
+    pub completed_at: Option<String>,
+    pub response_text: Option<String>,
+    /// Path to a PNG snapshot of the provider's page at the moment this
+    /// submission failed, if one was captured
+    pub failure_screenshot: Option<std::path::PathBuf>,
+}
+
+impl From<&Submission> for HistoryRecord {
+    fn from(submission: &Submission) -> Self {
+        Self {
+            provider_id: submission.provider_id,
+            prompt_content: submission.prompt_content.clone(),
+            status: submission.status,
+            attempt_count: submission.attempt_count,
+            error_type: submission.error_type,
+            error_message: submission.error_message.clone(),
+            started_at: submission.started_at.clone(),
+            completed_at: submission.completed_at.clone(),
+            response_text: submission.response_text.clone(),
+            failure_screenshot: submission.failure_screenshot.clone(),
+        }
+    }
+}
+
+/// All providers' results for one `submit_prompt` call, so the frontend can
+/// render ChatGPT/Gemini/Claude's answers to the same prompt side by side
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct HistoryBatch {
+    pub batch_id: String,
+    pub created_at: String,
+    /// Keyed by submission id, one entry per provider in the batch
+    pub records: HashMap<String, HistoryRecord>,
+}
+
+/// Lightweight summary of a batch for listing, without every provider's full response text
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct HistoryBatchSummary {
+    pub batch_id: String,
+    pub created_at: String,
+    pub provider_count: usize,
+}
+
+/// On-disk persistence for submission history, surviving app restarts.
+/// Backed by a single JSON file under the app config directory, rewritten in
+/// full on every update (batch history is small; no need for a real database).
+pub struct HistoryStore {
+    path: PathBuf,
+    batches: Mutex<Vec<HistoryBatch>>,
+}
+
+impl HistoryStore {
+    /// Loads existing history from `path` if present, otherwise starts empty
+    pub fn new(path: PathBuf) -> Self {
+        let batches = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+
+        Self {
+            path,
+            batches: Mutex::new(batches),
+        }
+    }
+
+    /// Records (or updates) a submission's result within its batch,
+    /// creating the batch if this is its first submission, and persists the
+    /// whole history store to disk afterwards
+    pub fn upsert_submission(
+        &self,
+        batch_id: &str,
+        submission: &Submission,
+    ) -> Result<(), CommandError> {
+        let mut batches = self.batches.lock().map_err(|e| {
+            CommandError::internal(format!("Failed to acquire lock: {}", e))
+        })?;
+
+        if !batches.iter().any(|b| b.batch_id == batch_id) {
+            batches.push(HistoryBatch {
+                batch_id: batch_id.to_string(),
+                created_at: chrono::Utc::now().to_rfc3339(),
+                records: HashMap::new(),
+            });
+        }
+
+        let batch = batches
+            .iter_mut()
+            .find(|b| b.batch_id == batch_id)
+            .expect("batch was just inserted if missing");
+
+        batch
+            .records
+            .insert(submission.id.clone(), HistoryRecord::from(submission));
+
+        self.persist(&batches)
+    }
+
+    /// Lists every recorded batch, most recently created first
+    pub fn list_batches(&self) -> Result<Vec<HistoryBatchSummary>, CommandError> {
+        let batches = self.batches.lock().map_err(|e| {
+            CommandError::internal(format!("Failed to acquire lock: {}", e))
+        })?;
+
+        let mut summaries: Vec<HistoryBatchSummary> = batches
+            .iter()
+            .map(|b| HistoryBatchSummary {
+                batch_id: b.batch_id.clone(),
+                created_at: b.created_at.clone(),
+                provider_count: b.records.len(),
+            })
+            .collect();
+
+        summaries.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+        Ok(summaries)
+    }
+
+    /// Fetches a single batch's provider-by-provider results
+    pub fn get_batch(&self, batch_id: &str) -> Result<HistoryBatch, CommandError> {
+        let batches = self.batches.lock().map_err(|e| {
+            CommandError::internal(format!("Failed to acquire lock: {}", e))
+        })?;
+
+        batches
+            .iter()
+            .find(|b| b.batch_id == batch_id)
+            .cloned()
+            .ok_or_else(|| CommandError::not_found(format!("Batch not found: {}", batch_id)))
+    }
+
+    /// Exports a batch as pretty-printed JSON
+    pub fn export_json(&self, batch_id: &str) -> Result<String, CommandError> {
+        let batch = self.get_batch(batch_id)?;
+
+        serde_json::to_string_pretty(&batch)
+            .map_err(|e| CommandError::internal(format!("Failed to serialize batch: {}", e)))
+    }
+
+    /// Exports a batch as Markdown, one section per provider, for
+    /// side-by-side comparison of how each provider answered the same prompt
+    pub fn export_markdown(&self, batch_id: &str) -> Result<String, CommandError> {
+        let batch = self.get_batch(batch_id)?;
+
+        let mut markdown = format!(
+            "# Batch {}\n\n_Created: {}_\n\n",
+            batch.batch_id, batch.created_at
+        );
+
+        let mut records: Vec<&HistoryRecord> = batch.records.values().collect();
+        records.sort_by_key(|r| r.provider_id.as_str());
+
+        for record in records {
+            markdown.push_str(&format!("## {}\n\n", record.provider_id.as_str()));
+            markdown.push_str(&format!("**Status:** {:?}\n\n", record.status));
+            markdown.push_str(&format!("**Prompt:**\n\n{}\n\n", record.prompt_content));
+
+            match &record.response_text {
+                Some(response) => markdown.push_str(&format!("**Response:**\n\n{}\n\n", response)),
+                None => markdown.push_str("**Response:** _(none captured)_\n\n"),
+            }
+        }
+
+        Ok(markdown)
+    }
+
+    /// Overwrites the history file with the current in-memory state
+    fn persist(&self, batches: &[HistoryBatch]) -> Result<(), CommandError> {
+        if let Some(parent) = self.path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+
+        let contents = serde_json::to_string_pretty(batches).map_err(|e| {
+            CommandError::internal(format!("Failed to serialize history: {}", e))
+        })?;
+
+        std::fs::write(&self.path, contents).map_err(|e| {
+            CommandError::internal(format!("Failed to write history to {:?}: {}", self.path, e))
+        })
+    }
+}
+
+/// Subscribes to a `StatusTracker`'s event stream and persists every updated
+/// submission that belongs to a batch, for the lifetime of the app
+pub fn bridge_tracker_to_history(
+    tracker: std::sync::Arc<crate::status::tracker::StatusTracker>,
+    store: std::sync::Arc<HistoryStore>,
+) {
+    tauri::async_runtime::spawn(async move {
+        let mut events = tracker.subscribe();
+
+        while let Ok(event) = events.recv().await {
+            if let crate::types::SubmissionEvent::Updated { submission } = event {
+                if let Some(batch_id) = submission.batch_id.clone() {
+                    if let Err(e) = store.upsert_submission(&batch_id, &submission) {
+                        crate::log_error!("Failed to persist submission history", {
+                            "submission_id": &submission.id,
+                            "error": e.to_string()
+                        });
+                    }
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("chenchen-history-test-{}-{}.json", name, uuid::Uuid::new_v4()))
+    }
+
+    fn submission(batch_id: &str) -> Submission {
+        let mut submission = Submission::new(ProviderId::ChatGPT, "Test prompt".to_string());
+        submission.batch_id = Some(batch_id.to_string());
+        submission
+    }
+
+    #[test]
+    fn test_upsert_and_list_batches() {
+        let store = HistoryStore::new(temp_path("list"));
+        store.upsert_submission("batch-1", &submission("batch-1")).unwrap();
+
+        let summaries = store.list_batches().unwrap();
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].batch_id, "batch-1");
+        assert_eq!(summaries[0].provider_count, 1);
+    }
+
+    #[test]
+    fn test_get_batch_not_found() {
+        let store = HistoryStore::new(temp_path("not-found"));
+        assert!(store.get_batch("missing").is_err());
+    }
+
+    #[test]
+    fn test_export_markdown_includes_provider_section() {
+        let store = HistoryStore::new(temp_path("markdown"));
+        let mut sub = submission("batch-2");
+        sub.response_text = Some("42".to_string());
+        store.upsert_submission("batch-2", &sub).unwrap();
+
+        let markdown = store.export_markdown("batch-2").unwrap();
+        assert!(markdown.contains("## ChatGPT"));
+        assert!(markdown.contains("42"));
+    }
+
+    #[test]
+    fn test_persists_across_store_instances() {
+        let path = temp_path("persist");
+        {
+            let store = HistoryStore::new(path.clone());
+            store.upsert_submission("batch-3", &submission("batch-3")).unwrap();
+        }
+
+        let reopened = HistoryStore::new(path);
+        let batch = reopened.get_batch("batch-3").unwrap();
+        assert_eq!(batch.records.len(), 1);
+    }
+}
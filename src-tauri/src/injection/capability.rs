@@ -0,0 +1,153 @@
+// Capability-based allowlist gating which injection scripts may run against
+// which provider origin, modeled on Tauri's ACL permissions/capabilities
+
+use crate::types::{CommandError, ProviderId};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A class of DOM operation an injection script is permitted to perform
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum InjectionOperation {
+    /// Setting the value of the prompt input element
+    SetInputValue,
+    /// Clicking the submit control
+    TriggerClick,
+}
+
+/// Describes what a generated script is allowed to do: which provider it
+/// targets, the exact origin it may run against, and which operation classes
+/// it may perform. Every `ExecutePromptPayload` must carry one, and `Injector`
+/// rejects any script whose target origin or operations fall outside it.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct InjectionCapability {
+    pub provider_id: ProviderId,
+    pub origin: String,
+    pub operations: Vec<InjectionOperation>,
+}
+
+impl InjectionCapability {
+    /// Builds the default capability for a provider: its own origin, allowed
+    /// to set the input value and trigger the submit click
+    pub fn default_for(provider_id: ProviderId) -> Self {
+        Self {
+            provider_id,
+            origin: provider_id.url().to_string(),
+            operations: vec![InjectionOperation::SetInputValue, InjectionOperation::TriggerClick],
+        }
+    }
+
+    /// Validates that this capability permits running `operations` against
+    /// `target_provider`, rejecting any mismatch in provider, origin, or
+    /// operation class
+    pub fn validate(
+        &self,
+        target_provider: ProviderId,
+        operations: &[InjectionOperation],
+    ) -> Result<(), CommandError> {
+        if self.provider_id != target_provider {
+            return Err(CommandError::validation(format!(
+                "Capability for {:?} cannot be used against {:?}",
+                self.provider_id, target_provider
+            )));
+        }
+
+        if self.origin != target_provider.url() {
+            return Err(CommandError::validation(format!(
+                "Capability origin {} does not match {:?}'s origin {}",
+                self.origin,
+                target_provider,
+                target_provider.url()
+            )));
+        }
+
+        for operation in operations {
+            if !self.operations.contains(operation) {
+                return Err(CommandError::validation(format!(
+                    "Capability for {:?} does not permit {:?}",
+                    target_provider, operation
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The granted capability for every configured provider, loaded alongside
+/// `ProviderConfigs` so a script built for one provider can never be
+/// dispatched into another provider's webview
+#[derive(Debug, Clone)]
+pub struct CapabilitySet {
+    capabilities: HashMap<ProviderId, InjectionCapability>,
+}
+
+impl CapabilitySet {
+    /// Builds the default capability set: one same-origin capability per
+    /// provider, permitting the standard input-set + submit-click operations
+    pub fn default_for_providers(provider_ids: &[ProviderId]) -> Self {
+        let capabilities = provider_ids
+            .iter()
+            .map(|&id| (id, InjectionCapability::default_for(id)))
+            .collect();
+
+        Self { capabilities }
+    }
+
+    /// Returns the granted capability for a provider, if any
+    pub fn get(&self, provider_id: ProviderId) -> Option<&InjectionCapability> {
+        self.capabilities.get(&provider_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_for_sets_matching_origin() {
+        let capability = InjectionCapability::default_for(ProviderId::Claude);
+        assert_eq!(capability.origin, ProviderId::Claude.url());
+    }
+
+    #[test]
+    fn test_validate_rejects_wrong_provider() {
+        let capability = InjectionCapability::default_for(ProviderId::Gemini);
+        let result = capability.validate(ProviderId::ChatGPT, &[InjectionOperation::SetInputValue]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_validate_rejects_disallowed_operation() {
+        let capability = InjectionCapability {
+            provider_id: ProviderId::ChatGPT,
+            origin: ProviderId::ChatGPT.url().to_string(),
+            operations: vec![InjectionOperation::SetInputValue],
+        };
+
+        let result = capability.validate(ProviderId::ChatGPT, &[InjectionOperation::TriggerClick]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_validate_passes_for_matching_capability() {
+        let capability = InjectionCapability::default_for(ProviderId::ChatGPT);
+        let result = capability.validate(
+            ProviderId::ChatGPT,
+            &[InjectionOperation::SetInputValue, InjectionOperation::TriggerClick],
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_capability_set_isolates_providers() {
+        let set = CapabilitySet::default_for_providers(&[ProviderId::ChatGPT, ProviderId::Gemini]);
+
+        let chatgpt_capability = set.get(ProviderId::ChatGPT).unwrap();
+        assert!(chatgpt_capability
+            .validate(ProviderId::Gemini, &[InjectionOperation::SetInputValue])
+            .is_err());
+
+        assert!(set.get(ProviderId::Claude).is_none());
+    }
+}
@@ -1,21 +1,44 @@
 // Injector for executing JavaScript in webview contexts
 // Handles script execution, timeout management, and result parsing
 
+use super::capability::InjectionOperation;
 use super::script_builder;
 use super::InjectionResult;
-use crate::log_error;
-use crate::log_info;
-use crate::types::ProviderId;
-use crate::webview::WebviewManager;
-use tauri::AppHandle;
+use crate::{log_error, log_info};
+use crate::types::{CommandError, ExecutePromptPayload, ProviderId};
+use crate::webview::manager::WebviewManager;
+
+/// Operations every generated injection script performs: it sets the input
+/// value and then triggers the submit click
+const STANDARD_OPERATIONS: &[InjectionOperation] =
+    &[InjectionOperation::SetInputValue, InjectionOperation::TriggerClick];
+
+/// Extra headroom `execute()` allows on top of the injector's own
+/// `timeout_ms` before giving up on a script: the script's internal
+/// `waitForElement` deadline already fires at `timeout_ms`, so this margin
+/// gives its `report_execution_result` call a chance to land before the
+/// outer deadline pre-empts it.
+const EXECUTE_TIMEOUT_MARGIN_MS: u64 = 2_000;
 
 /// Manages JavaScript injection into provider webviews
-pub struct Injector {}
+pub struct Injector {
+    /// How long a generated injection script waits for an element to appear
+    /// before giving up, overridable via `with_timeout`
+    timeout_ms: u64,
+}
 
 impl Injector {
     /// Creates a new Injector instance
     pub fn new() -> Result<Self, String> {
-        Ok(Self {})
+        Ok(Self {
+            timeout_ms: script_builder::DEFAULT_INJECTION_TIMEOUT_MS,
+        })
+    }
+
+    /// Overrides the default element-wait timeout used by `prepare_injection`
+    pub fn with_timeout(mut self, timeout_ms: u64) -> Self {
+        self.timeout_ms = timeout_ms;
+        self
     }
 
     /// Prepares an injection script for execution
@@ -24,9 +47,15 @@ impl Injector {
     /// Use this to prepare scripts that will be executed later via webview.eval()
     ///
     /// # Arguments
-    /// * `input_selectors` - CSS selectors for finding the prompt input element
-    /// * `submit_selectors` - CSS selectors for finding the submit button
+    /// * `input_selectors` - CSS selectors for finding the prompt input element (tried in order as fallbacks)
+    /// * `submit_selectors` - CSS selectors for finding the submit button (tried in order as fallbacks)
     /// * `prompt` - The prompt text to inject
+    /// * `submission_id` - The submission this execution result belongs to
+    /// * `provider_id` - The provider the script runs against
+    ///
+    /// Behind the `tracing` feature, this opens a `tracing` span recording
+    /// `provider_id`, `script_length` and `elapsed_ms`, complementing the
+    /// `log_info!` calls below rather than replacing them.
     ///
     /// # Returns
     /// A JavaScript code string ready for execution
@@ -35,67 +64,306 @@ impl Injector {
         input_selectors: &[String],
         submit_selectors: &[String],
         prompt: &str,
+        submission_id: &str,
+        provider_id: ProviderId,
     ) -> String {
+        // Complements the `log_info!` calls below rather than replacing
+        // them: a no-op unless a downstream app opts into the `tracing`
+        // feature and installs its own `tracing` subscriber, mirroring how
+        // wry gates its own `evaluate_script` spans behind an opt-in flag.
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!(
+            "injector_prepare_injection",
+            provider_id = %provider_id.as_str(),
+            script_length = tracing::field::Empty,
+            elapsed_ms = tracing::field::Empty,
+        )
+        .entered();
+        #[cfg(feature = "tracing")]
+        let started = std::time::Instant::now();
+
         log_info!("Preparing injection script", {
             "input_selectors_count": input_selectors.len(),
             "submit_selectors_count": submit_selectors.len(),
-            "prompt_length": prompt.len()
+            "prompt_length": prompt.len(),
+            "timeout_ms": self.timeout_ms
         });
 
-        let script =
-            script_builder::generate_injection_script(input_selectors, submit_selectors, prompt);
+        let script = script_builder::generate_injection_script(
+            input_selectors,
+            submit_selectors,
+            prompt,
+            submission_id,
+            provider_id.as_str(),
+            self.timeout_ms,
+        );
 
         log_info!("Injection script generated", {
             "script_length": script.len()
         });
 
+        #[cfg(feature = "tracing")]
+        {
+            let span = tracing::Span::current();
+            span.record("script_length", script.len());
+            span.record("elapsed_ms", started.elapsed().as_millis() as u64);
+        }
+
         script
     }
 
+    /// Prepares an injection script the same way `prepare_injection` does,
+    /// except `args` is passed into the script as a structured `__args`
+    /// object (via `JSON.parse`) instead of being spliced into the script
+    /// source as a string. Prefer this over `prepare_injection` for prompts
+    /// that aren't guaranteed to be free of backticks, quotes, `</script>`,
+    /// template-literal `${}` sequences, or newlines -- `args` is expected to
+    /// carry at least a `prompt` field, mirroring how `prompt` is read by the
+    /// script `prepare_injection` generates.
+    ///
+    /// # Arguments
+    /// * `input_selectors` - CSS selectors for finding the prompt input element (tried in order as fallbacks)
+    /// * `submit_selectors` - CSS selectors for finding the submit button (tried in order as fallbacks)
+    /// * `args` - Structured arguments (at minimum `{ "prompt": "..." }`) passed to the script
+    /// * `submission_id` - The submission this execution result belongs to
+    /// * `provider_id` - The provider the script runs against
+    ///
+    /// # Returns
+    /// A JavaScript code string ready for execution
+    pub fn prepare_injection_with_args(
+        &self,
+        input_selectors: &[String],
+        submit_selectors: &[String],
+        args: &serde_json::Value,
+        submission_id: &str,
+        provider_id: ProviderId,
+    ) -> String {
+        log_info!("Preparing injection script with structured args", {
+            "input_selectors_count": input_selectors.len(),
+            "submit_selectors_count": submit_selectors.len(),
+            "timeout_ms": self.timeout_ms
+        });
+
+        let script = script_builder::generate_injection_script_with_args(
+            input_selectors,
+            submit_selectors,
+            args,
+            submission_id,
+            provider_id.as_str(),
+            self.timeout_ms,
+        );
+
+        log_info!("Injection script generated", {
+            "script_length": script.len()
+        });
+
+        script
+    }
+
+    /// Prepares an injection script the same way `prepare_injection` does,
+    /// except the generated script's `waitForElement` poll interval is
+    /// caller-configurable instead of pinned to the script builder's default,
+    /// so a caller can trade polling frequency against CPU overhead for a
+    /// particular provider.
+    ///
+    /// # Arguments
+    /// * `input_selectors` - CSS selectors for finding the prompt input element (tried in order as fallbacks)
+    /// * `submit_selectors` - CSS selectors for finding the submit button (tried in order as fallbacks)
+    /// * `prompt` - The prompt text to inject
+    /// * `submission_id` - The submission this execution result belongs to
+    /// * `provider_id` - The provider the script runs against
+    /// * `poll_interval_ms` - How often the script re-checks selectors as a `MutationObserver` fallback
+    ///
+    /// # Returns
+    /// A JavaScript code string ready for execution
+    pub fn prepare_injection_with_wait(
+        &self,
+        input_selectors: &[String],
+        submit_selectors: &[String],
+        prompt: &str,
+        submission_id: &str,
+        provider_id: ProviderId,
+        poll_interval_ms: u64,
+    ) -> String {
+        log_info!("Preparing injection script with custom poll interval", {
+            "input_selectors_count": input_selectors.len(),
+            "submit_selectors_count": submit_selectors.len(),
+            "prompt_length": prompt.len(),
+            "timeout_ms": self.timeout_ms,
+            "poll_interval_ms": poll_interval_ms
+        });
+
+        let script = script_builder::generate_injection_script_with_wait(
+            input_selectors,
+            submit_selectors,
+            prompt,
+            submission_id,
+            provider_id.as_str(),
+            self.timeout_ms,
+            poll_interval_ms,
+        );
+
+        log_info!("Injection script generated", {
+            "script_length": script.len()
+        });
+
+        script
+    }
+
+    /// Prepares a response-harvesting script for execution
+    ///
+    /// This generates the JavaScript code but does not execute it, the same
+    /// way `prepare_injection` does. The script watches the provider's
+    /// response container and reports the captured text back via
+    /// `report_response` once generation finishes.
+    ///
+    /// # Arguments
+    /// * `response_selectors` - CSS selectors for the response container
+    /// * `completion_selectors` - CSS selectors whose disappearance signals completion
+    /// * `submission_id` - The submission this harvested response belongs to
+    /// * `provider_id` - The provider to harvest the response from
+    ///
+    /// # Returns
+    /// A JavaScript code string ready for execution
+    pub fn prepare_harvest(
+        &self,
+        response_selectors: &[String],
+        completion_selectors: &[String],
+        submission_id: &str,
+        provider_id: ProviderId,
+    ) -> String {
+        log_info!("Preparing harvest script", {
+            "provider_id": format!("{:?}", provider_id),
+            "submission_id": submission_id
+        });
+
+        script_builder::generate_harvest_script(
+            response_selectors,
+            completion_selectors,
+            submission_id,
+            provider_id.as_str(),
+        )
+    }
+
+    /// Prepares a document-start capture script meant to be registered as a
+    /// webview initialization script (it must run before the provider page's
+    /// own JS, so it's in place no matter how early the response container
+    /// mounts) rather than executed on demand like `prepare_injection` or
+    /// `prepare_harvest`. It installs a `MutationObserver` on the response
+    /// container and posts incremental `ResponseChunk`s back through Tauri's
+    /// IPC as the model streams its answer, instead of the one-shot
+    /// debounced text `prepare_harvest` reports.
+    ///
+    /// # Arguments
+    /// * `response_selectors` - CSS selectors for the response container (tried in order)
+    /// * `completion_selectors` - CSS selectors whose disappearance signals completion
+    /// * `provider_id` - The provider this capture script is running inside
+    ///
+    /// # Returns
+    /// A JavaScript code string to register as a webview initialization script
+    pub fn prepare_init_script(
+        &self,
+        response_selectors: &[String],
+        completion_selectors: &[String],
+        provider_id: ProviderId,
+    ) -> String {
+        log_info!("Preparing document-start capture script", {
+            "provider_id": format!("{:?}", provider_id),
+            "response_selectors_count": response_selectors.len(),
+            "completion_selectors_count": completion_selectors.len()
+        });
+
+        script_builder::generate_streaming_capture_script(
+            response_selectors,
+            completion_selectors,
+            provider_id.as_str(),
+        )
+    }
+
+    /// Validates that an `ExecutePromptPayload`'s capability actually grants
+    /// it permission to run against its own `provider_id` with the standard
+    /// set-input-value + trigger-click operations, rejecting any mismatch
+    /// before the script is ever dispatched to a webview
+    pub fn authorize(&self, payload: &ExecutePromptPayload) -> Result<(), CommandError> {
+        payload
+            .capability
+            .validate(payload.provider_id, STANDARD_OPERATIONS)
+    }
+
     /// Executes an injection script in an existing webview
     ///
-    /// This executes the JavaScript code in the provider's webview and parses the result.
-    /// The script should return a JSON object with the InjectionResult structure.
+    /// This executes the JavaScript code in the provider's webview and waits
+    /// for its real, round-tripped `InjectionResult` (see
+    /// `WebviewManager::execute_script`). The wait is bounded by `self.timeout_ms`
+    /// plus `EXECUTE_TIMEOUT_MARGIN_MS`: `execute_script` has its own internal
+    /// round-trip deadline, but this outer one guards against a hung eval
+    /// (e.g. the webview's JS engine wedged) that never even reaches it.
+    ///
+    /// Before dispatch, `script` is run through `script_builder::isolate_script`
+    /// so it executes inside its own provider-namespaced scope rather than
+    /// sharing lexical bindings with whatever another provider's (or an
+    /// earlier) injection left behind in the same webview.
+    ///
+    /// Behind the `tracing` feature, this opens a `tracing` span recording
+    /// `provider_id`, `script_length`, `success`, `element_found`,
+    /// `submit_triggered` and `elapsed_ms`, complementing the `log_info!`/
+    /// `log_error!` calls below rather than replacing them.
     ///
     /// # Arguments
-    /// * `app` - The Tauri app handle
+    /// * `app` - Forwarded to `webview_manager.execute_script` so it can
+    ///   resolve the provider's live `"{provider}-webview"` child webview
     /// * `webview_manager` - The webview manager
     /// * `provider_id` - The provider to execute the script for
     /// * `script` - The JavaScript code to execute
     ///
     /// # Returns
-    /// Result containing the parsed InjectionResult or an error message
+    /// Result containing the InjectionResult, or an error message if the
+    /// script failed or the deadline elapsed first
     pub async fn execute(
         &self,
-        app: &AppHandle,
+        app: &tauri::AppHandle,
         webview_manager: &WebviewManager,
         provider_id: ProviderId,
         script: &str,
     ) -> Result<InjectionResult, String> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!(
+            "injector_execute",
+            provider_id = %provider_id.as_str(),
+            script_length = script.len(),
+            success = tracing::field::Empty,
+            element_found = tracing::field::Empty,
+            submit_triggered = tracing::field::Empty,
+            elapsed_ms = tracing::field::Empty,
+        )
+        .entered();
+        #[cfg(feature = "tracing")]
+        let started = std::time::Instant::now();
+
         log_info!("Executing injection script", {
             "provider_id": format!("{:?}", provider_id),
             "script_length": script.len()
         });
 
-        // Execute the script in the existing webview
-        let result_str = webview_manager
-            .execute_script(app, provider_id, script)
-            .await?;
+        let isolated_script = script_builder::isolate_script(script, provider_id.as_str());
 
-        log_info!("Raw script result", {
-            "provider_id": format!("{:?}", provider_id),
-            "result": &result_str
-        });
+        let deadline_ms = self.timeout_ms + EXECUTE_TIMEOUT_MARGIN_MS;
+        let outcome = tokio::time::timeout(
+            std::time::Duration::from_millis(deadline_ms),
+            webview_manager.execute_script(app, provider_id, &isolated_script),
+        )
+        .await;
 
-        // Parse the JSON result
-        let result: InjectionResult = serde_json::from_str(&result_str).map_err(|e| {
-            log_error!("Failed to parse injection result", {
-                "provider_id": format!("{:?}", provider_id),
-                "error": e.to_string(),
-                "result": &result_str
-            });
-            format!("Failed to parse injection result: {}", e)
-        })?;
+        let result = match outcome {
+            Ok(inner) => inner?,
+            Err(_) => {
+                log_error!("Injection execution timed out", {
+                    "provider_id": format!("{:?}", provider_id),
+                    "deadline_ms": deadline_ms
+                });
+                return Err(format!("injection timed out after {} ms", deadline_ms));
+            }
+        };
 
         log_info!("Injection execution completed", {
             "provider_id": format!("{:?}", provider_id),
@@ -104,6 +372,15 @@ impl Injector {
             "submit_triggered": result.submit_triggered
         });
 
+        #[cfg(feature = "tracing")]
+        {
+            let span = tracing::Span::current();
+            span.record("success", result.success);
+            span.record("element_found", result.element_found);
+            span.record("submit_triggered", result.submit_triggered);
+            span.record("elapsed_ms", started.elapsed().as_millis() as u64);
+        }
+
         Ok(result)
     }
 
@@ -122,6 +399,7 @@ impl Injector {
             error_message: None,
             element_found: true,
             submit_triggered: true,
+            data: None,
         };
 
         log_info!("Injection execution completed", {
@@ -157,12 +435,89 @@ mod tests {
             &vec!["input".to_string()],
             &vec!["button".to_string()],
             "Test",
+            "sub-1",
+            ProviderId::ChatGPT,
         );
 
         assert!(!script.is_empty());
         assert!(script.contains("querySelector"));
     }
 
+    #[test]
+    fn test_prepare_injection_with_args_generates_script() {
+        let injector = Injector::new().unwrap();
+        let args = serde_json::json!({ "prompt": "Test" });
+        let script = injector.prepare_injection_with_args(
+            &vec!["input".to_string()],
+            &vec!["button".to_string()],
+            &args,
+            "sub-1",
+            ProviderId::ChatGPT,
+        );
+
+        assert!(!script.is_empty());
+        assert!(script.contains("JSON.parse("));
+        assert!(script.contains("__args.prompt"));
+    }
+
+    #[test]
+    fn test_prepare_injection_with_wait_uses_custom_poll_interval() {
+        let injector = Injector::new().unwrap();
+        let script = injector.prepare_injection_with_wait(
+            &vec!["input".to_string()],
+            &vec!["button".to_string()],
+            "Test",
+            "sub-1",
+            ProviderId::ChatGPT,
+            250,
+        );
+
+        assert!(script.contains("pollIntervalMs = 250"));
+    }
+
+    #[test]
+    fn test_with_timeout_overrides_default() {
+        let injector = Injector::new().unwrap().with_timeout(5_000);
+        let script = injector.prepare_injection(
+            &vec!["input".to_string()],
+            &vec!["button".to_string()],
+            "Test",
+            "sub-1",
+            ProviderId::ChatGPT,
+        );
+
+        assert!(script.contains("5000"));
+    }
+
+    #[test]
+    fn test_prepare_harvest_generates_script() {
+        let injector = Injector::new().unwrap();
+        let script = injector.prepare_harvest(
+            &vec![".response".to_string()],
+            &vec![".stop-generating".to_string()],
+            "sub-1",
+            ProviderId::ChatGPT,
+        );
+
+        assert!(!script.is_empty());
+        assert!(script.contains("MutationObserver"));
+        assert!(script.contains("sub-1"));
+    }
+
+    #[test]
+    fn test_prepare_init_script_generates_streaming_capture_script() {
+        let injector = Injector::new().unwrap();
+        let script = injector.prepare_init_script(
+            &vec![".response".to_string()],
+            &vec![".stop-generating".to_string()],
+            ProviderId::ChatGPT,
+        );
+
+        assert!(!script.is_empty());
+        assert!(script.contains("MutationObserver"));
+        assert!(script.contains("report_response_chunk"));
+    }
+
     #[test]
     fn test_execute_mock_returns_success() {
         let injector = Injector::new().unwrap();
@@ -172,4 +527,30 @@ mod tests {
         let injection_result = result.unwrap();
         assert!(injection_result.success);
     }
+
+    #[test]
+    fn test_authorize_allows_matching_capability() {
+        let injector = Injector::new().unwrap();
+        let payload = ExecutePromptPayload {
+            submission_id: "sub-1".to_string(),
+            provider_id: ProviderId::ChatGPT,
+            script: "noop".to_string(),
+            capability: super::capability::InjectionCapability::default_for(ProviderId::ChatGPT),
+        };
+
+        assert!(injector.authorize(&payload).is_ok());
+    }
+
+    #[test]
+    fn test_authorize_rejects_mismatched_provider() {
+        let injector = Injector::new().unwrap();
+        let payload = ExecutePromptPayload {
+            submission_id: "sub-1".to_string(),
+            provider_id: ProviderId::ChatGPT,
+            script: "noop".to_string(),
+            capability: super::capability::InjectionCapability::default_for(ProviderId::Gemini),
+        };
+
+        assert!(injector.authorize(&payload).is_err());
+    }
 }
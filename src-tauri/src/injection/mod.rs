@@ -11,7 +11,13 @@ pub struct InjectionResult {
     pub error_message: Option<String>,
     pub element_found: bool,
     pub submit_triggered: bool,
+    /// Arbitrary structured payload for scripts that report more than a
+    /// prompt injection's pass/fail (e.g. `WebviewManager::verify_contract`'s
+    /// per-expectation results). Absent for ordinary injection scripts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
 }
 
+pub mod capability;
 pub mod injector;
 pub mod script_builder;
@@ -1,19 +1,210 @@
 // JavaScript code generation for element location and prompt submission
 // Generates scripts that try selectors in order until elements are found
 
-/// Generates a JavaScript injection script to submit a prompt to an LLM provider
+/// Default timeout for waiting for an element to appear, matching
+/// WebDriver's typical explicit-wait window
+pub(crate) const DEFAULT_INJECTION_TIMEOUT_MS: u64 = 10_000;
+
+/// How often `waitForElement` polls as a fallback alongside its
+/// `MutationObserver`, in case the observer misses a synchronous DOM replace
+const POLL_INTERVAL_MS: u64 = 100;
+
+/// Shared template behind `generate_injection_script`/`_with_wait`/`_with_args`:
+/// waits for the input element the way a WebDriver explicit wait would (polls
+/// on an interval and also attaches a `MutationObserver`, so it reacts the
+/// instant a SPA provider mounts its input asynchronously), sets its value via
+/// the native value setter (bypassing React/Vue's value interceptor) with
+/// `input`/`change` events dispatched afterwards, then locates and clicks the
+/// submit control the same way, checking it's enabled first. The script never
+/// returns synchronously; once it settles it reports the outcome back via
+/// `report_execution_result`.
 ///
-/// The generated script:
-/// 1. Tries each input selector in order until an element is found
-/// 2. Sets the prompt value in the input element
-/// 3. Tries each submit selector in order until a button is found
-/// 4. Clicks the submit button
-/// 5. Returns a result object with success status
+/// `prompt_expr` is the JS expression the script reads the prompt text from --
+/// a pre-escaped string literal for the splice-the-prompt-in variants, or
+/// `__args.prompt` for the structured-args variant. `iife_params`/`iife_args`
+/// let that same variant wrap the whole script in
+/// `(function(__args) {...})(JSON.parse(...))` instead of a bare
+/// `(function() {...})()`.
+#[allow(clippy::too_many_arguments)]
+fn build_injection_script(
+    input_selectors: &[String],
+    submit_selectors: &[String],
+    prompt_expr: &str,
+    iife_params: &str,
+    iife_args: &str,
+    submission_id: &str,
+    provider_id: &str,
+    timeout_ms: u64,
+    poll_interval_ms: u64,
+) -> String {
+    format!(
+        r#"
+(function({iife_params}) {{
+    const inputSelectors = {input_selectors};
+    const submitSelectors = {submit_selectors};
+    const prompt = {prompt_expr};
+    const submissionId = {submission_id};
+    const providerId = {provider_id};
+    const timeoutMs = {timeout_ms};
+    const pollIntervalMs = {poll_interval_ms};
+
+    function findFirstMatch(selectors) {{
+        for (let i = 0; i < selectors.length; i++) {{
+            const element = document.querySelector(selectors[i]);
+            if (element) return element;
+        }}
+        return null;
+    }}
+
+    function isEnabled(element) {{
+        return !element.disabled && element.getAttribute('aria-disabled') !== 'true';
+    }}
+
+    // Borrows the WebDriver wait-for-element discipline: poll on an
+    // interval and also react instantly via MutationObserver, whichever
+    // fires first, until timeoutMs elapses.
+    function waitForElement(selectors) {{
+        return new Promise(function(resolve) {{
+            const immediate = findFirstMatch(selectors);
+            if (immediate) {{
+                resolve(immediate);
+                return;
+            }}
+
+            let settled = false;
+            function settle(element) {{
+                if (settled) return;
+                settled = true;
+                clearInterval(pollTimer);
+                observer.disconnect();
+                clearTimeout(timeoutTimer);
+                resolve(element);
+            }}
+
+            const pollTimer = setInterval(function() {{
+                const found = findFirstMatch(selectors);
+                if (found) settle(found);
+            }}, pollIntervalMs);
+
+            const observer = new MutationObserver(function() {{
+                const found = findFirstMatch(selectors);
+                if (found) settle(found);
+            }});
+            observer.observe(document.body, {{ childList: true, subtree: true }});
+
+            const timeoutTimer = setTimeout(function() {{ settle(null); }}, timeoutMs);
+        }});
+    }}
+
+    // Uses the native value setter so React/Vue-controlled inputs (which
+    // override the `value` property descriptor) still register the change
+    function setNativeValue(element, value) {{
+        const isTextarea = element.tagName === 'TEXTAREA';
+        const isInput = element.tagName === 'INPUT';
+
+        if (isTextarea || isInput) {{
+            const prototype = isTextarea ? window.HTMLTextAreaElement.prototype : window.HTMLInputElement.prototype;
+            const nativeSetter = Object.getOwnPropertyDescriptor(prototype, 'value').set;
+            nativeSetter.call(element, value);
+        }} else {{
+            element.textContent = value;
+        }}
+
+        element.dispatchEvent(new Event('input', {{ bubbles: true }}));
+        element.dispatchEvent(new Event('change', {{ bubbles: true }}));
+    }}
+
+    function report(result) {{
+        if (window.__TAURI__ && window.__TAURI__.core) {{
+            window.__TAURI__.core.invoke('report_execution_result', {{
+                payload: {{
+                    submission_id: submissionId,
+                    provider_id: providerId,
+                    success: result.success,
+                    error_message: result.error_message,
+                    element_found: result.element_found,
+                    submit_triggered: result.submit_triggered
+                }}
+            }});
+        }}
+    }}
+
+    (async function() {{
+        try {{
+            const inputElement = await waitForElement(inputSelectors);
+            if (!inputElement) {{
+                report({{
+                    success: false,
+                    error_message: 'Input element not found within ' + timeoutMs + 'ms. Tried selectors: ' + inputSelectors.join(', '),
+                    element_found: false,
+                    submit_triggered: false
+                }});
+                return;
+            }}
+
+            setNativeValue(inputElement, prompt);
+
+            const submitButton = await waitForElement(submitSelectors);
+            if (!submitButton) {{
+                report({{
+                    success: false,
+                    error_message: 'Submit button not found within ' + timeoutMs + 'ms. Tried selectors: ' + submitSelectors.join(', '),
+                    element_found: true,
+                    submit_triggered: false
+                }});
+                return;
+            }}
+
+            if (!isEnabled(submitButton)) {{
+                report({{
+                    success: false,
+                    error_message: 'Submit button found but is disabled',
+                    element_found: true,
+                    submit_triggered: false
+                }});
+                return;
+            }}
+
+            submitButton.click();
+
+            report({{
+                success: true,
+                error_message: null,
+                element_found: true,
+                submit_triggered: true
+            }});
+        }} catch (error) {{
+            report({{
+                success: false,
+                error_message: 'JavaScript error: ' + error.message,
+                element_found: false,
+                submit_triggered: false
+            }});
+        }}
+    }})();
+}})({iife_args});
+"#,
+        input_selectors = format_selector_array(input_selectors),
+        submit_selectors = format_selector_array(submit_selectors),
+        prompt_expr = prompt_expr,
+        submission_id = escape_for_javascript(submission_id),
+        provider_id = escape_for_javascript(provider_id),
+        timeout_ms = timeout_ms,
+        poll_interval_ms = poll_interval_ms,
+        iife_params = iife_params,
+        iife_args = iife_args,
+    )
+}
+
+/// Generates a JavaScript injection script to submit a prompt to an LLM provider
 ///
 /// # Arguments
 /// * `input_selectors` - CSS selectors for the prompt input element (tried in order)
 /// * `submit_selectors` - CSS selectors for the submit button (tried in order)
 /// * `prompt` - The text to inject into the input element
+/// * `submission_id` - The submission this execution result belongs to
+/// * `provider_id` - The provider the script runs against
+/// * `timeout_ms` - How long to wait for each element before giving up
 ///
 /// # Returns
 /// A JavaScript code string that can be executed via webview.eval()
@@ -21,113 +212,331 @@ pub fn generate_injection_script(
     input_selectors: &[String],
     submit_selectors: &[String],
     prompt: &str,
+    submission_id: &str,
+    provider_id: &str,
+    timeout_ms: u64,
 ) -> String {
-    // Escape the prompt text for safe JavaScript string embedding
     let escaped_prompt = escape_for_javascript(prompt);
 
+    build_injection_script(
+        input_selectors,
+        submit_selectors,
+        &escaped_prompt,
+        "",
+        "",
+        submission_id,
+        provider_id,
+        timeout_ms,
+        POLL_INTERVAL_MS,
+    )
+}
+
+/// Generates a JavaScript injection script the same way `generate_injection_script`
+/// does, except the `waitForElement` poll interval is caller-configurable
+/// instead of being pinned to `POLL_INTERVAL_MS`.
+///
+/// # Arguments
+/// * `input_selectors` - CSS selectors for the prompt input element (tried in order)
+/// * `submit_selectors` - CSS selectors for the submit button (tried in order)
+/// * `prompt` - The text to inject into the input element
+/// * `submission_id` - The submission this execution result belongs to
+/// * `provider_id` - The provider the script runs against
+/// * `timeout_ms` - How long to wait for each element before giving up
+/// * `poll_interval_ms` - How often `waitForElement` re-checks selectors as a
+///   fallback alongside its `MutationObserver`
+///
+/// # Returns
+/// A JavaScript code string that can be executed via webview.eval()
+#[allow(clippy::too_many_arguments)]
+pub fn generate_injection_script_with_wait(
+    input_selectors: &[String],
+    submit_selectors: &[String],
+    prompt: &str,
+    submission_id: &str,
+    provider_id: &str,
+    timeout_ms: u64,
+    poll_interval_ms: u64,
+) -> String {
+    let escaped_prompt = escape_for_javascript(prompt);
+
+    build_injection_script(
+        input_selectors,
+        submit_selectors,
+        &escaped_prompt,
+        "",
+        "",
+        submission_id,
+        provider_id,
+        timeout_ms,
+        poll_interval_ms,
+    )
+}
+
+/// Generates a JavaScript injection script the same way `generate_injection_script`
+/// does, except the prompt (and any other caller-supplied data) is passed as a
+/// structured `__args` object instead of being spliced into the script source.
+///
+/// `args` is serialized with `serde_json::to_string` and then re-escaped as a
+/// JS string literal (double-serialize), so it can be embedded inside
+/// `JSON.parse("...")` and handed to the wrapping IIFE as `__args` -- this
+/// mirrors how Playwright's `page.evaluate(fn, arg)` passes arguments, and
+/// avoids the manual string-escaping `generate_injection_script` relies on
+/// for the prompt text. `args` is expected to carry at least a `prompt` field;
+/// the script reads it as `__args.prompt`.
+///
+/// # Arguments
+/// * `input_selectors` - CSS selectors for the prompt input element (tried in order)
+/// * `submit_selectors` - CSS selectors for the submit button (tried in order)
+/// * `args` - Structured arguments (at minimum `{ "prompt": "..." }`) passed to the script
+/// * `submission_id` - The submission this execution result belongs to
+/// * `provider_id` - The provider the script runs against
+/// * `timeout_ms` - How long to wait for each element before giving up
+///
+/// # Returns
+/// A JavaScript code string that can be executed via webview.eval()
+pub fn generate_injection_script_with_args(
+    input_selectors: &[String],
+    submit_selectors: &[String],
+    args: &serde_json::Value,
+    submission_id: &str,
+    provider_id: &str,
+    timeout_ms: u64,
+) -> String {
+    let escaped_args = escape_args_for_javascript(args);
+    let iife_args = format!("JSON.parse({})", escaped_args);
+
+    build_injection_script(
+        input_selectors,
+        submit_selectors,
+        "__args.prompt",
+        "__args",
+        &iife_args,
+        submission_id,
+        provider_id,
+        timeout_ms,
+        POLL_INTERVAL_MS,
+    )
+}
+
+/// How long the response text must stop mutating before it's considered complete
+const DEBOUNCE_MS: u64 = 1500;
+
+/// Generates a JavaScript harvesting script that watches a provider's
+/// response container for the model's streamed answer and reports it back
+/// to Rust once generation finishes
+///
+/// The generated script:
+/// 1. Tries each response selector in order until a container element is found
+/// 2. Attaches a `MutationObserver` to the container, debouncing on each mutation
+/// 3. Treats the answer as complete once mutations stop for `DEBOUNCE_MS`, or
+///    once every completion selector (e.g. a "stop generating" button) is gone
+/// 4. Posts the captured text back via `report_response`
+///
+/// # Arguments
+/// * `response_selectors` - CSS selectors for the response container (tried in order)
+/// * `completion_selectors` - CSS selectors whose disappearance signals completion
+/// * `submission_id` - The submission this harvested response belongs to
+/// * `provider_id` - The provider the response was harvested from
+///
+/// # Returns
+/// A JavaScript code string that can be executed via webview.eval()
+pub fn generate_harvest_script(
+    response_selectors: &[String],
+    completion_selectors: &[String],
+    submission_id: &str,
+    provider_id: &str,
+) -> String {
     format!(
         r#"
 (function() {{
-    try {{
-        // Try each input selector until we find an element
-        let inputElement = null;
-        const inputSelectors = {input_selectors};
-
-        for (let i = 0; i < inputSelectors.length; i++) {{
-            const selector = inputSelectors[i];
-            inputElement = document.querySelector(selector);
-            if (inputElement) {{
-                console.log('Found input element with selector:', selector);
-                break;
-            }}
+    const responseSelectors = {response_selectors};
+    const completionSelectors = {completion_selectors};
+    const submissionId = {submission_id};
+    const providerId = {provider_id};
+    const debounceMs = {debounce_ms};
+
+    let container = null;
+    for (let i = 0; i < responseSelectors.length; i++) {{
+        container = document.querySelector(responseSelectors[i]);
+        if (container) break;
+    }}
+
+    if (!container) {{
+        console.error('Response container not found. Tried selectors: ' + responseSelectors.join(', '));
+        return;
+    }}
+
+    function isStillGenerating() {{
+        return completionSelectors.some(function(selector) {{
+            return document.querySelector(selector) !== null;
+        }});
+    }}
+
+    function reportResponse() {{
+        const payload = {{
+            submission_id: submissionId,
+            provider_id: providerId,
+            response_text: container.innerText
+        }};
+
+        if (window.__TAURI__ && window.__TAURI__.core) {{
+            window.__TAURI__.core.invoke('report_response', {{ payload: payload }});
         }}
+    }}
 
-        if (!inputElement) {{
-            return {{
-                success: false,
-                error_message: 'Input element not found. Tried selectors: ' + inputSelectors.join(', '),
-                element_found: false,
-                submit_triggered: false
-            }};
+    let debounceTimer = null;
+
+    function scheduleCheck() {{
+        if (debounceTimer) clearTimeout(debounceTimer);
+
+        debounceTimer = setTimeout(function() {{
+            if (!isStillGenerating()) {{
+                observer.disconnect();
+                reportResponse();
+            }} else {{
+                scheduleCheck();
+            }}
+        }}, debounceMs);
+    }}
+
+    const observer = new MutationObserver(scheduleCheck);
+    observer.observe(container, {{ childList: true, subtree: true, characterData: true }});
+
+    scheduleCheck();
+}})();
+"#,
+        response_selectors = format_selector_array(response_selectors),
+        completion_selectors = format_selector_array(completion_selectors),
+        submission_id = escape_for_javascript(submission_id),
+        provider_id = escape_for_javascript(provider_id),
+        debounce_ms = DEBOUNCE_MS,
+    )
+}
+
+/// How often the streaming capture script re-checks for the response
+/// container as a fallback alongside its `MutationObserver`, mirroring
+/// `POLL_INTERVAL_MS`'s role in `waitForElement`
+const STREAM_POLL_INTERVAL_MS: u64 = 100;
+
+/// Generates a document-start capture script meant to be registered as a
+/// webview initialization script (running before the provider page's own JS
+/// executes, the way wry/tao's `initialization_script` does), so it never
+/// races the page's first paint the way a post-load harvest script can.
+///
+/// Unlike `generate_harvest_script`, which debounces and reports the full
+/// response text exactly once after generation settles, this script reports
+/// *every* observed change as soon as it happens: each `MutationObserver`
+/// callback diffs the container's current `innerText` against what was last
+/// reported and posts only the new suffix as a `ResponseChunk`'s `delta` via
+/// `report_response_chunk`, so the Rust side can render partial text as the
+/// model streams its answer. A final chunk with `done: true` is posted once
+/// every completion selector (e.g. a "stop generating" button) disappears.
+///
+/// # Arguments
+/// * `response_selectors` - CSS selectors for the response container (tried in order)
+/// * `completion_selectors` - CSS selectors whose disappearance signals completion
+/// * `provider_id` - The provider this capture script is running inside
+///
+/// # Returns
+/// A JavaScript code string to register as a webview initialization script
+pub fn generate_streaming_capture_script(
+    response_selectors: &[String],
+    completion_selectors: &[String],
+    provider_id: &str,
+) -> String {
+    format!(
+        r#"
+(function() {{
+    const responseSelectors = {response_selectors};
+    const completionSelectors = {completion_selectors};
+    const providerId = {provider_id};
+    const pollIntervalMs = {poll_interval_ms};
+
+    function findContainer() {{
+        for (let i = 0; i < responseSelectors.length; i++) {{
+            const element = document.querySelector(responseSelectors[i]);
+            if (element) return element;
         }}
+        return null;
+    }}
 
-        // Set the prompt value
-        // Handle both input/textarea elements and contenteditable divs
-        if (inputElement.tagName === 'TEXTAREA' || inputElement.tagName === 'INPUT') {{
-            inputElement.value = {escaped_prompt};
-            // Trigger input event for frameworks that listen to it
-            inputElement.dispatchEvent(new Event('input', {{ bubbles: true }}));
-            inputElement.dispatchEvent(new Event('change', {{ bubbles: true }}));
-        }} else if (inputElement.isContentEditable || inputElement.getAttribute('contenteditable') === 'true') {{
-            inputElement.textContent = {escaped_prompt};
-            // Trigger input event for contenteditable elements
-            inputElement.dispatchEvent(new Event('input', {{ bubbles: true }}));
-        }} else {{
-            // Fallback: try setting value
-            inputElement.value = {escaped_prompt};
-            inputElement.dispatchEvent(new Event('input', {{ bubbles: true }}));
+    function isStillGenerating() {{
+        return completionSelectors.some(function(selector) {{
+            return document.querySelector(selector) !== null;
+        }});
+    }}
+
+    function postChunk(delta, done) {{
+        if (delta === '' && !done) return;
+
+        if (window.__TAURI__ && window.__TAURI__.core) {{
+            window.__TAURI__.core.invoke('report_response_chunk', {{
+                payload: {{ provider_id: providerId, delta: delta, done: done }}
+            }});
         }}
+    }}
 
-        console.log('Set prompt value in input element');
+    // The response container does not exist yet at document-start, so wait
+    // for it the same way `waitForElement` does: poll on an interval and
+    // react instantly via a root-level MutationObserver, whichever fires first.
+    function waitForContainer(callback) {{
+        const immediate = findContainer();
+        if (immediate) {{
+            callback(immediate);
+            return;
+        }}
 
-        // Small delay to allow any reactive frameworks to process the input
-        setTimeout(function() {{
-            // Try each submit selector until we find a button
-            let submitButton = null;
-            const submitSelectors = {submit_selectors};
+        const rootObserver = new MutationObserver(function() {{
+            const found = findContainer();
+            if (found) {{
+                rootObserver.disconnect();
+                clearInterval(pollTimer);
+                callback(found);
+            }}
+        }});
+        rootObserver.observe(document.documentElement, {{ childList: true, subtree: true }});
 
-            for (let i = 0; i < submitSelectors.length; i++) {{
-                const selector = submitSelectors[i];
-                submitButton = document.querySelector(selector);
-                if (submitButton) {{
-                    console.log('Found submit button with selector:', selector);
-                    break;
-                }}
+        const pollTimer = setInterval(function() {{
+            const found = findContainer();
+            if (found) {{
+                rootObserver.disconnect();
+                clearInterval(pollTimer);
+                callback(found);
             }}
+        }}, pollIntervalMs);
+    }}
 
-            if (!submitButton) {{
-                return {{
-                    success: false,
-                    error_message: 'Submit button not found. Tried selectors: ' + submitSelectors.join(', '),
-                    element_found: true,
-                    submit_triggered: false
-                }};
+    waitForContainer(function(container) {{
+        let lastReportedLength = 0;
+
+        function reportGrowth() {{
+            const text = container.innerText;
+            if (text.length > lastReportedLength) {{
+                const delta = text.slice(lastReportedLength);
+                lastReportedLength = text.length;
+                postChunk(delta, false);
             }}
+        }}
 
-            // Click the submit button
-            submitButton.click();
-            console.log('Clicked submit button');
+        const contentObserver = new MutationObserver(function() {{
+            reportGrowth();
 
-            return {{
-                success: true,
-                error_message: null,
-                element_found: true,
-                submit_triggered: true
-            }};
-        }}, 100);
-
-        // Return success for the input setting part
-        return {{
-            success: true,
-            error_message: null,
-            element_found: true,
-            submit_triggered: false
-        }};
+            if (!isStillGenerating()) {{
+                contentObserver.disconnect();
+                reportGrowth();
+                postChunk('', true);
+            }}
+        }});
+        contentObserver.observe(container, {{ childList: true, subtree: true, characterData: true }});
 
-    }} catch (error) {{
-        console.error('Injection script error:', error);
-        return {{
-            success: false,
-            error_message: 'JavaScript error: ' + error.message,
-            element_found: false,
-            submit_triggered: false
-        }};
-    }}
+        reportGrowth();
+    }});
 }})();
 "#,
-        input_selectors = format_selector_array(input_selectors),
-        submit_selectors = format_selector_array(submit_selectors),
-        escaped_prompt = escaped_prompt,
+        response_selectors = format_selector_array(response_selectors),
+        completion_selectors = format_selector_array(completion_selectors),
+        provider_id = escape_for_javascript(provider_id),
+        poll_interval_ms = STREAM_POLL_INTERVAL_MS,
     )
 }
 
@@ -148,6 +557,16 @@ fn escape_for_javascript(text: &str) -> String {
     format!(r#""{}""#, escaped)
 }
 
+/// Serializes `args` to a JSON string, then serializes that string again so
+/// it embeds safely as a string literal inside `JSON.parse("...")` -- this
+/// double-serialize is what lets the resulting script tolerate prompts (or
+/// any other field) containing backticks, quotes, `</script>`, `${}`, or
+/// newlines without any manual escaping at the call site.
+fn escape_args_for_javascript(args: &serde_json::Value) -> String {
+    let json = serde_json::to_string(args).unwrap_or_else(|_| "null".to_string());
+    serde_json::to_string(&json).unwrap_or_else(|_| r#""null""#.to_string())
+}
+
 /// Formats an array of selectors as a JavaScript array literal
 fn format_selector_array(selectors: &[String]) -> String {
     let quoted_selectors: Vec<String> = selectors
@@ -158,6 +577,29 @@ fn format_selector_array(selectors: &[String]) -> String {
     format!("[{}]", quoted_selectors.join(", "))
 }
 
+/// Wraps `script` in a freshly-scoped, uniquely-named IIFE so its lexical
+/// bindings (and any accidental `var` leak) can't collide with another
+/// provider's injection running in the same webview -- borrows the "each
+/// init script gets its own context" approach Tauri uses for plugin scripts.
+/// `script` is expected to already be a complete, self-executing statement
+/// (as every `generate_*` function in this module produces); this just adds
+/// one more provider-namespaced layer around it.
+pub fn isolate_script(script: &str, provider_id: &str) -> String {
+    format!(
+        "(function __chenchen_scope_{namespace}() {{\n{script}\n}})();",
+        namespace = sanitize_for_identifier(provider_id),
+        script = script,
+    )
+}
+
+/// Replaces every character that isn't a valid JS identifier character with
+/// `_`, so an arbitrary provider id can be safely spliced into a function name
+fn sanitize_for_identifier(raw: &str) -> String {
+    raw.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -191,12 +633,72 @@ mod tests {
         assert_eq!(result, r#"["input", "textarea"]"#);
     }
 
+    #[test]
+    fn test_isolate_script_wraps_in_a_provider_named_iife() {
+        let isolated = isolate_script("console.log('hi');", "ChatGPT");
+
+        assert!(isolated.contains("function __chenchen_scope_ChatGPT()"));
+        assert!(isolated.contains("console.log('hi');"));
+    }
+
+    #[test]
+    fn test_isolate_script_sanitizes_non_identifier_characters() {
+        let isolated = isolate_script("1;", "Chat GPT-4!");
+
+        assert!(isolated.contains("function __chenchen_scope_Chat_GPT_4_()"));
+    }
+
+    #[test]
+    fn test_isolate_script_gives_distinct_providers_distinct_scope_names() {
+        let chatgpt = isolate_script("1;", "ChatGPT");
+        let gemini = isolate_script("1;", "Gemini");
+
+        assert_ne!(chatgpt, gemini);
+        assert!(!chatgpt.contains("__chenchen_scope_Gemini"));
+    }
+
+    #[test]
+    fn test_generate_harvest_script_includes_selectors_and_ids() {
+        let script = generate_harvest_script(
+            &vec![".response".to_string()],
+            &vec![".stop-generating".to_string()],
+            "sub-123",
+            "ChatGPT",
+        );
+
+        assert!(script.contains(".response"));
+        assert!(script.contains(".stop-generating"));
+        assert!(script.contains("sub-123"));
+        assert!(script.contains("ChatGPT"));
+        assert!(script.contains("MutationObserver"));
+        assert!(script.contains("report_response"));
+    }
+
+    #[test]
+    fn test_generate_streaming_capture_script_reports_deltas_via_report_response_chunk() {
+        let script = generate_streaming_capture_script(
+            &vec![".response".to_string()],
+            &vec![".stop-generating".to_string()],
+            "ChatGPT",
+        );
+
+        assert!(script.contains(".response"));
+        assert!(script.contains(".stop-generating"));
+        assert!(script.contains("ChatGPT"));
+        assert!(script.contains("MutationObserver"));
+        assert!(script.contains("report_response_chunk"));
+        assert!(script.contains("done: true"));
+    }
+
     #[test]
     fn test_generate_script_basic() {
         let script = generate_injection_script(
             &vec!["input".to_string()],
             &vec!["button".to_string()],
             "Hello",
+            "sub-1",
+            "ChatGPT",
+            DEFAULT_INJECTION_TIMEOUT_MS,
         );
 
         assert!(script.contains("input"));
@@ -205,4 +707,75 @@ mod tests {
         assert!(script.contains("querySelector"));
         assert!(script.contains("click"));
     }
+
+    #[test]
+    fn test_generate_injection_script_with_wait_uses_the_given_poll_interval() {
+        let script = generate_injection_script_with_wait(
+            &vec!["input".to_string()],
+            &vec!["button".to_string()],
+            "Hello",
+            "sub-1",
+            "ChatGPT",
+            5_000,
+            250,
+        );
+
+        assert!(script.contains("setInterval"));
+        assert!(script.contains("MutationObserver"));
+        assert!(script.contains("pollIntervalMs = 250"));
+        assert!(script.contains("timeoutMs = 5000"));
+    }
+
+    #[test]
+    fn test_generate_injection_script_with_args_embeds_args_via_json_parse() {
+        let args = serde_json::json!({ "prompt": "Hello" });
+        let script = generate_injection_script_with_args(
+            &vec!["input".to_string()],
+            &vec!["button".to_string()],
+            &args,
+            "sub-1",
+            "ChatGPT",
+            DEFAULT_INJECTION_TIMEOUT_MS,
+        );
+
+        assert!(script.contains("function(__args)"));
+        assert!(script.contains("JSON.parse("));
+        assert!(script.contains("__args.prompt"));
+        assert!(script.contains("Hello"));
+    }
+
+    #[test]
+    fn test_escape_args_for_javascript_round_trips_hostile_prompt_characters() {
+        let hostile = "`</script>` ${injected} \"quoted\"\nnewline";
+        let args = serde_json::json!({ "prompt": hostile });
+
+        let escaped = escape_args_for_javascript(&args);
+
+        // The hostile text must never appear unescaped; it's only reachable
+        // by parsing the JS string literal, then parsing the JSON it contains.
+        assert!(!escaped.contains("</script>`"));
+        let inner_json: String = serde_json::from_str(&escaped).unwrap();
+        let reparsed: serde_json::Value = serde_json::from_str(&inner_json).unwrap();
+        assert_eq!(reparsed["prompt"], hostile);
+    }
+
+    #[test]
+    fn test_generate_injection_script_waits_for_elements() {
+        let script = generate_injection_script(
+            &vec!["textarea".to_string()],
+            &vec!["button".to_string()],
+            "Hello",
+            "sub-1",
+            "ChatGPT",
+            5_000,
+        );
+
+        assert!(script.contains("MutationObserver"));
+        assert!(script.contains("setInterval"));
+        assert!(script.contains("getOwnPropertyDescriptor"));
+        assert!(script.contains("report_execution_result"));
+        assert!(script.contains("5000"));
+        assert!(script.contains("sub-1"));
+        assert!(script.contains("ChatGPT"));
+    }
 }
@@ -1,7 +1,7 @@
 // Layout calculation logic
 // Calculates split-screen panel dimensions based on provider count
 
-use super::{LayoutConfiguration, LayoutType, PanelDimension};
+use super::{LayoutConfiguration, LayoutType, PanelBounds, PanelDimension};
 use crate::types::ProviderId;
 use log::info;
 
@@ -54,3 +54,55 @@ pub fn calculate_layout(providers: &[ProviderId]) -> LayoutConfiguration {
 
     LayoutConfiguration::new(provider_count, layout_type, panel_dimensions)
 }
+
+/// Converts a layout's fractional panel dimensions into absolute pixel
+/// bounds for the given viewport size, ready to hand to `set_bounds` so every
+/// provider webview can be re-anchored to its panel in a single batch
+pub fn to_pixel_bounds(
+    layout: &LayoutConfiguration,
+    viewport_width: f64,
+    viewport_height: f64,
+) -> Vec<PanelBounds> {
+    layout
+        .panel_dimensions
+        .iter()
+        .map(|panel| PanelBounds {
+            provider_id: panel.provider_id,
+            x: panel.x as f64 * viewport_width,
+            y: panel.y as f64 * viewport_height,
+            width: panel.width as f64 * viewport_width,
+            height: panel.height as f64 * viewport_height,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_pixel_bounds_scales_by_viewport() {
+        let layout = calculate_layout(&[ProviderId::ChatGPT, ProviderId::Gemini]);
+        let bounds = to_pixel_bounds(&layout, 1000.0, 800.0);
+
+        assert_eq!(bounds.len(), 2);
+        assert_eq!(bounds[0].provider_id, ProviderId::ChatGPT);
+        assert_eq!(bounds[0].x, 0.0);
+        assert_eq!(bounds[0].width, 500.0);
+        assert_eq!(bounds[1].x, 500.0);
+        assert_eq!(bounds[1].width, 500.0);
+        assert_eq!(bounds[0].height, 800.0);
+    }
+
+    #[test]
+    fn test_to_pixel_bounds_full_layout_covers_viewport() {
+        let layout = calculate_layout(&[ProviderId::Claude]);
+        let bounds = to_pixel_bounds(&layout, 1280.0, 720.0);
+
+        assert_eq!(bounds.len(), 1);
+        assert_eq!(bounds[0].x, 0.0);
+        assert_eq!(bounds[0].y, 0.0);
+        assert_eq!(bounds[0].width, 1280.0);
+        assert_eq!(bounds[0].height, 720.0);
+    }
+}
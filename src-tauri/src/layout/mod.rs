@@ -41,6 +41,18 @@ impl PanelDimension {
     }
 }
 
+/// Absolute pixel bounds for a single provider webview panel, derived from a
+/// `PanelDimension`'s percentages and the current viewport size. What
+/// `sync_layout` hands to `WebviewWindow::set_bounds`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PanelBounds {
+    pub provider_id: ProviderId,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
 /// Complete layout configuration for all selected providers
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct LayoutConfiguration {
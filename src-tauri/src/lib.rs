@@ -1,11 +1,17 @@
 use log::{error, info};
 
 // Module declarations
+pub mod campaign;
 pub mod commands;
+pub mod contract;
+pub mod diagnostics;
+pub mod history;
 pub mod injection;
 pub mod layout;
 pub mod logging;
 pub mod providers;
+pub mod retry;
+pub mod rules;
 pub mod state;
 pub mod status;
 pub mod types;
@@ -32,6 +38,78 @@ pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .manage(AppState::new()) // Register shared application state
+        .setup(|app| {
+            use std::sync::Arc;
+            use tauri::Manager;
+
+            let app_state = app.state::<AppState>();
+            let status_tracker = Arc::clone(&app_state.status_tracker);
+            status::tracker::bridge_events_to_webview(Arc::clone(&status_tracker), app.handle().clone());
+            status::tracker::spawn_timeout_loop(Arc::clone(&status_tracker));
+            history::bridge_tracker_to_history(Arc::clone(&status_tracker), Arc::clone(&app_state.history));
+            webview::manager::bridge_failures_to_screenshots(
+                Arc::clone(&status_tracker),
+                Arc::clone(&app_state.webview_manager),
+                app.handle().clone(),
+            );
+            webview::manager::spawn_auth_polling_loop(
+                app.handle().clone(),
+                Arc::clone(&app_state.webview_manager),
+                Arc::clone(&app_state.provider_manager),
+            );
+
+            if let Some(provider_configs) = app_state.provider_configs.lock().unwrap().clone() {
+                let scheduler = Arc::new(retry::RetryScheduler::new(
+                    status_tracker,
+                    Arc::new(provider_configs),
+                    Arc::new(app_state.capabilities.clone()),
+                    Arc::clone(&app_state.webview_manager),
+                ));
+                retry::spawn_retry_loop(app.handle().clone(), scheduler);
+            } else {
+                error!("Skipping retry loop: provider configurations not loaded");
+            }
+
+            // Child webviews don't follow their parent window automatically,
+            // so re-anchor every panel whenever the main window is resized.
+            // There's no equivalent native event for the host page scrolling
+            // its content; the frontend is expected to call `sync_layout`
+            // directly for that case.
+            if let Some(main_window) = app.get_window("main") {
+                let app_handle = app.handle().clone();
+                main_window.on_window_event(move |event| {
+                    if let tauri::WindowEvent::Resized(size) = event {
+                        let app_state = app_handle.state::<AppState>();
+                        let selected_providers: Vec<types::ProviderId> = {
+                            let manager = app_state.provider_manager.lock().unwrap();
+                            manager.get_selected_providers().iter().map(|p| p.id).collect()
+                        };
+
+                        if selected_providers.is_empty() {
+                            return;
+                        }
+
+                        let scale_factor = app_handle
+                            .get_window("main")
+                            .and_then(|w| w.scale_factor().ok())
+                            .unwrap_or(1.0);
+                        let logical = size.to_logical::<f64>(scale_factor);
+                        let layout = layout::calculator::calculate_layout(&selected_providers);
+
+                        if let Err(e) = app_state.webview_manager.apply_layout(
+                            &app_handle,
+                            &layout,
+                            logical.width,
+                            logical.height,
+                        ) {
+                            error!("Failed to re-anchor panels after resize: {}", e);
+                        }
+                    }
+                });
+            }
+
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             greet,
             commands::get_providers,
@@ -40,8 +118,24 @@ pub fn run() {
             commands::submit_prompt,
             commands::get_submission_status,
             commands::report_execution_result,
+            commands::report_response,
+            commands::report_script_result,
+            commands::report_response_chunk,
             commands::sync_provider_webview,
+            commands::sync_layout,
             commands::dispose_provider_webview,
+            commands::run_selector_diagnostics,
+            commands::verify_provider_selectors,
+            commands::verify_provider_contract,
+            commands::reload_provider_configs,
+            commands::retry_submission,
+            commands::list_history_batches,
+            commands::get_history_batch,
+            commands::export_history_batch,
+            commands::create_campaign,
+            commands::list_campaigns,
+            commands::get_campaign_results,
+            commands::delete_campaign,
         ])
         .run(tauri::generate_context!())
         .unwrap_or_else(|err| {
@@ -5,6 +5,9 @@ use serde::{Serialize, Deserialize};
 use serde_json;
 use log::{info, warn, error};
 
+#[cfg(feature = "otel")]
+pub mod otel;
+
 /// Log levels matching the standard log crate
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -23,8 +26,52 @@ pub struct StructuredLog {
     pub timestamp: String,
 }
 
+/// A span opened around a provider operation (e.g. a selection change in
+/// `ProviderManager`, a script execution in `WebviewManager`) so every
+/// `StructuredLog` emitted while it's alive automatically carries the same
+/// `trace_id`/`span_id` in its context, for correlating log lines with the
+/// OTEL trace across an operation. A no-op when the `otel` feature is off,
+/// so call sites don't need their own feature gate.
+#[cfg(feature = "otel")]
+pub struct Span(otel::OtelSpanGuard);
+#[cfg(not(feature = "otel"))]
+pub struct Span;
+
+/// Opens a `Span` named `name`. See `Span` for what carries through to logs.
+#[cfg(feature = "otel")]
+pub fn start_span(name: &'static str) -> Span {
+    Span(otel::start_span(name))
+}
+#[cfg(not(feature = "otel"))]
+pub fn start_span(_name: &'static str) -> Span {
+    Span
+}
+
+/// Records a duration metric (e.g. auth-status latency) as an OTEL
+/// histogram. A no-op when the `otel` feature is off.
+#[cfg(feature = "otel")]
+pub fn record_duration_metric(name: &'static str, duration_ms: u64) {
+    otel::record_duration_metric(name, duration_ms);
+}
+#[cfg(not(feature = "otel"))]
+pub fn record_duration_metric(_name: &'static str, _duration_ms: u64) {}
+
 impl StructuredLog {
     pub fn new(level: LogLevel, message: String, context: serde_json::Value) -> Self {
+        // Additive: only fills in trace/span ids when otel is enabled and a
+        // span is currently open, and never overwrites caller-supplied keys.
+        #[cfg(feature = "otel")]
+        let context = {
+            let mut context = context;
+            if let serde_json::Value::Object(ref mut map) = context {
+                if let Some((trace_id, span_id)) = otel::current_trace_context() {
+                    map.entry("trace_id").or_insert(serde_json::Value::String(trace_id));
+                    map.entry("span_id").or_insert(serde_json::Value::String(span_id));
+                }
+            }
+            context
+        };
+
         Self {
             level,
             message,
@@ -66,6 +113,9 @@ impl StructuredLog {
                 error!(target: "structured", "{}", json_output);
             }
         }
+
+        #[cfg(feature = "otel")]
+        otel::forward_log_record(self);
     }
 }
 
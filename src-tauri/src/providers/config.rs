@@ -4,6 +4,14 @@ use crate::types::{CommandError, ProviderId};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Deep-merges a higher-priority layer (`other`) over `self`, keeping
+/// lower-priority values for anything the higher layer omits. Mirrors
+/// Anchor's layered-config `Merge` trait.
+pub trait Merge {
+    fn merge(&mut self, other: Self);
+}
 
 /// CSS selectors and configuration for locating elements on a provider's website
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -15,11 +23,73 @@ pub struct ProviderSelectorConfig {
     pub input_selectors: Vec<String>,
     pub submit_selectors: Vec<String>,
     pub auth_check_selectors: Vec<String>,
+    /// CSS selectors for the element that receives the model's streamed
+    /// response text, tried in order like the other selector lists
+    pub response_selectors: Vec<String>,
+    /// CSS selectors for the element (e.g. a "stop generating" button) whose
+    /// disappearance signals the response finished streaming
+    pub completion_selectors: Vec<String>,
     pub last_updated: String, // ISO 8601 timestamp
     #[serde(skip_serializing_if = "Option::is_none")]
     pub notes: Option<String>,
 }
 
+impl Merge for ProviderSelectorConfig {
+    /// Overrides `input_selectors`/`submit_selectors`/`auth_check_selectors`
+    /// when the higher layer provides them, keeping the lower layer's values
+    /// for anything the higher layer leaves empty. The higher `config_version`
+    /// wins when both layers set one.
+    fn merge(&mut self, other: Self) {
+        if !other.input_selectors.is_empty() {
+            self.input_selectors = other.input_selectors;
+        }
+        if !other.submit_selectors.is_empty() {
+            self.submit_selectors = other.submit_selectors;
+        }
+        if !other.auth_check_selectors.is_empty() {
+            self.auth_check_selectors = other.auth_check_selectors;
+        }
+        if !other.response_selectors.is_empty() {
+            self.response_selectors = other.response_selectors;
+        }
+        if !other.completion_selectors.is_empty() {
+            self.completion_selectors = other.completion_selectors;
+        }
+        if is_newer_semver(&other.version, &self.version) {
+            self.version = other.version;
+        }
+        if other.notes.is_some() {
+            self.notes = other.notes;
+        }
+        self.last_updated = other.last_updated;
+    }
+}
+
+/// Compares two MAJOR.MINOR.PATCH version strings; returns true if `candidate`
+/// is strictly newer than `current`. Unparseable versions never win.
+fn is_newer_semver(candidate: &str, current: &str) -> bool {
+    fn parts(version: &str) -> Option<(u32, u32, u32)> {
+        let mut parts = version.split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next()?.parse().ok()?;
+        let patch = parts.next()?.parse().ok()?;
+        Some((major, minor, patch))
+    }
+
+    match (parts(candidate), parts(current)) {
+        (Some(c), Some(cur)) => c > cur,
+        _ => false,
+    }
+}
+
+/// Wraps a loaded value with the filesystem path it came from, so callers can
+/// report which layer a config was sourced from and re-read it on reload.
+#[derive(Debug, Clone)]
+pub struct WithPath<T> {
+    pub value: T,
+    pub path: PathBuf,
+}
+
 /// Container for all provider configurations
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ProviderConfigs {
@@ -27,6 +97,25 @@ pub struct ProviderConfigs {
     pub providers: HashMap<String, ProviderSelectorConfig>,
 }
 
+impl Merge for ProviderConfigs {
+    /// Deep-merges each provider's selector config, and adds any providers
+    /// present only in the higher layer
+    fn merge(&mut self, other: Self) {
+        if is_newer_semver(&other.version, &self.version) {
+            self.version = other.version.clone();
+        }
+
+        for (key, overlay_config) in other.providers {
+            match self.providers.get_mut(&key) {
+                Some(base_config) => base_config.merge(overlay_config),
+                None => {
+                    self.providers.insert(key, overlay_config);
+                }
+            }
+        }
+    }
+}
+
 impl ProviderConfigs {
     /// Loads provider configurations from the config/providers.json file
     /// Uses compile-time embedding to ensure the config is available in production builds
@@ -45,6 +134,38 @@ impl ProviderConfigs {
         Ok(configs)
     }
 
+    /// Loads the embedded defaults, then deep-merges a user-editable
+    /// `providers.json` from the app config directory over them if one
+    /// exists. Returns the merged config tagged with whichever path it was
+    /// sourced from last, so `AppState::reload_provider_configs` can re-read
+    /// just that layer without restarting the app.
+    pub fn load_layered(user_config_path: &Path) -> Result<WithPath<Self>, CommandError> {
+        let mut configs = Self::load()?;
+        let mut source_path = PathBuf::new();
+
+        if user_config_path.exists() {
+            let contents = std::fs::read_to_string(user_config_path).map_err(|e| {
+                CommandError::internal(format!(
+                    "Failed to read user provider config at {:?}: {}",
+                    user_config_path, e
+                ))
+            })?;
+
+            let overlay: ProviderConfigs = serde_json::from_str(&contents).map_err(|e| {
+                CommandError::internal(format!("Failed to parse user provider config: {}", e))
+            })?;
+
+            configs.merge(overlay);
+            configs.validate()?;
+            source_path = user_config_path.to_path_buf();
+        }
+
+        Ok(WithPath {
+            value: configs,
+            path: source_path,
+        })
+    }
+
     /// Validates the loaded configurations
     fn validate(&self) -> Result<(), CommandError> {
         // Validate version format (semver)
@@ -84,6 +205,18 @@ impl ProviderConfigs {
                     key
                 )));
             }
+            if config.response_selectors.is_empty() {
+                return Err(CommandError::validation(format!(
+                    "response_selectors cannot be empty for provider {}",
+                    key
+                )));
+            }
+            if config.completion_selectors.is_empty() {
+                return Err(CommandError::validation(format!(
+                    "completion_selectors cannot be empty for provider {}",
+                    key
+                )));
+            }
         }
 
         Ok(())
@@ -111,3 +244,95 @@ impl ProviderConfigs {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(version: &str, input: &[&str]) -> ProviderSelectorConfig {
+        ProviderSelectorConfig {
+            provider_id: ProviderId::ChatGPT,
+            version: version.to_string(),
+            is_selected: true,
+            input_selectors: input.iter().map(|s| s.to_string()).collect(),
+            submit_selectors: vec!["button".to_string()],
+            auth_check_selectors: vec![".login".to_string()],
+            response_selectors: vec![".response".to_string()],
+            completion_selectors: vec![".stop-generating".to_string()],
+            last_updated: "2024-01-01T00:00:00Z".to_string(),
+            notes: None,
+        }
+    }
+
+    #[test]
+    fn test_merge_overrides_only_nonempty_fields() {
+        let mut base = config("1.0.0", &["textarea#base"]);
+        let overlay = ProviderSelectorConfig {
+            input_selectors: vec!["textarea#overlay".to_string()],
+            submit_selectors: vec![],
+            ..config("1.0.0", &[])
+        };
+
+        base.merge(overlay);
+
+        assert_eq!(base.input_selectors, vec!["textarea#overlay".to_string()]);
+        assert_eq!(base.submit_selectors, vec!["button".to_string()]);
+    }
+
+    #[test]
+    fn test_merge_keeps_higher_version() {
+        let mut base = config("1.0.0", &["textarea"]);
+        let overlay = config("2.0.0", &[]);
+
+        base.merge(overlay);
+
+        assert_eq!(base.version, "2.0.0");
+    }
+
+    #[test]
+    fn test_merge_provider_configs_adds_new_providers() {
+        let mut base = ProviderConfigs {
+            version: "1.0.0".to_string(),
+            providers: HashMap::new(),
+        };
+        base.providers
+            .insert("ChatGPT".to_string(), config("1.0.0", &["textarea"]));
+
+        let mut overlay = ProviderConfigs {
+            version: "1.0.0".to_string(),
+            providers: HashMap::new(),
+        };
+        overlay.providers.insert(
+            "ChatGPT".to_string(),
+            config("1.0.0", &["textarea#overlay"]),
+        );
+        overlay
+            .providers
+            .insert("Gemini".to_string(), config("1.0.0", &["textarea"]));
+
+        base.merge(overlay);
+
+        assert_eq!(base.providers.len(), 2);
+        assert_eq!(
+            base.providers["ChatGPT"].input_selectors,
+            vec!["textarea#overlay".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_is_newer_semver() {
+        assert!(is_newer_semver("1.1.0", "1.0.9"));
+        assert!(!is_newer_semver("1.0.0", "1.0.0"));
+        assert!(!is_newer_semver("not-a-version", "1.0.0"));
+    }
+
+    #[test]
+    fn test_load_layered_without_user_file_matches_embedded_defaults() {
+        let result = ProviderConfigs::load_layered(Path::new("/nonexistent/providers.json"));
+        let embedded = ProviderConfigs::load().unwrap();
+
+        let layered = result.unwrap();
+        assert_eq!(layered.value.version, embedded.version);
+        assert_eq!(layered.path, PathBuf::new());
+    }
+}
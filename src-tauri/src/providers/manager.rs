@@ -1,23 +1,144 @@
 // Provider selection and management logic
 
-use super::Provider;
+use super::config::ProviderConfigs;
+use super::{Observer, Provider, ProviderEvent};
 use crate::types::{CommandError, ProviderId};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex, Weak};
 
-/// Manages the three LLM providers and their selection state
+/// Built-in provider registry, used until a TOML/JSON registry file is
+/// supplied. Each entry is exactly what a config-loaded entry would be:
+/// a `ProviderId`/name/URL pair plus the selectors
+/// `WebviewManager::generate_auth_check_script` uses to detect a logged-in
+/// session.
+pub fn default_registry() -> Vec<Provider> {
+    vec![
+        Provider::new(ProviderId::ChatGPT, vec![
+            "nav[aria-label='Chat history']".to_string(),
+            "button[data-testid='profile-button']".to_string(),
+        ]),
+        Provider::new(ProviderId::Gemini, vec![
+            "div[data-test-id='bard-mode-menu-button']".to_string(),
+        ]),
+        Provider::new(ProviderId::Claude, vec![
+            "button[data-testid='user-menu-button']".to_string(),
+        ]),
+    ]
+}
+
+/// Manages the configured LLM providers and their selection state
 pub struct ProviderManager {
     providers: Vec<Provider>,
+    /// Maximum number of providers that may be selected at once. Defaults to
+    /// the number of configured providers, so a registry of N providers
+    /// never has to special-case a literal "3".
+    max_selected: usize,
+    observers: Mutex<Vec<Weak<dyn Observer>>>,
 }
 
 impl ProviderManager {
-    /// Creates a new ProviderManager with all three providers initialized
+    /// Creates a new ProviderManager from the built-in three-provider registry
     pub fn new() -> Self {
-        Self {
-            providers: vec![
-                Provider::new(ProviderId::ChatGPT),
-                Provider::new(ProviderId::Gemini),
-                Provider::new(ProviderId::Claude),
-            ],
+        Self::from_registry(default_registry())
+            .expect("built-in provider registry is always valid")
+    }
+
+    /// Builds a `ProviderManager` from a declarative registry (e.g. loaded
+    /// from a TOML/JSON config file). Validates that provider IDs are
+    /// unique and each entry has a non-empty URL and at least one auth
+    /// selector before accepting the set. `max_selected` defaults to the
+    /// number of entries.
+    pub fn from_registry(providers: Vec<Provider>) -> Result<Self, CommandError> {
+        if providers.is_empty() {
+            return Err(CommandError::validation(
+                "Provider registry must configure at least one provider",
+            ));
         }
+
+        let mut seen_ids = HashSet::new();
+        for provider in &providers {
+            if !seen_ids.insert(provider.id) {
+                return Err(CommandError::validation(format!(
+                    "Duplicate provider id in registry: {:?}",
+                    provider.id
+                )));
+            }
+            if provider.url.trim().is_empty() {
+                return Err(CommandError::validation(format!(
+                    "Provider {:?} must have a non-empty URL",
+                    provider.id
+                )));
+            }
+            if provider.auth_selectors.is_empty() {
+                return Err(CommandError::validation(format!(
+                    "Provider {:?} must configure at least one auth selector",
+                    provider.id
+                )));
+            }
+        }
+
+        let max_selected = providers.len();
+
+        Ok(Self {
+            providers,
+            max_selected,
+            observers: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Builds a `ProviderManager` from an already-loaded `ProviderConfigs`
+    /// (the embedded `config/providers.json` defaults, deep-merged with any
+    /// user overrides) instead of the hardcoded `default_registry`, so the
+    /// registry's selection/auth-check selectors track whatever that file
+    /// currently says for ChatGPT/Gemini/Claude. `ProviderId` is still a
+    /// closed enum, so this doesn't let config introduce a new provider --
+    /// only re-describe the fixed three. Each `ProviderSelectorConfig`
+    /// becomes a `Provider` carrying its `auth_check_selectors` and
+    /// `is_selected` flag; validation and `max_selected` are still whatever
+    /// `from_registry` enforces.
+    pub fn from_provider_configs(configs: &ProviderConfigs) -> Result<Self, CommandError> {
+        let providers = configs
+            .providers
+            .values()
+            .map(|config| {
+                let mut provider =
+                    Provider::new(config.provider_id, config.auth_check_selectors.clone());
+                provider.is_selected = config.is_selected;
+                provider
+            })
+            .collect();
+
+        Self::from_registry(providers)
+    }
+
+    /// Registers `observer` to receive `ProviderEvent`s. The manager only
+    /// holds a `Weak` reference, so letting the caller's `Arc` drop is
+    /// enough to unsubscribe.
+    pub fn subscribe(&self, observer: &Arc<dyn Observer>) {
+        self.observers.lock().unwrap().push(Arc::downgrade(observer));
+    }
+
+    /// Removes `observer` from the subscriber list, if still present.
+    pub fn unsubscribe(&self, observer: &Arc<dyn Observer>) {
+        let target = Arc::as_ptr(observer);
+        self.observers
+            .lock()
+            .unwrap()
+            .retain(|weak| !matches!(weak.upgrade(), Some(o) if Arc::as_ptr(&o) == target));
+    }
+
+    /// Fans `event` out to every still-live observer, pruning any whose
+    /// `Arc` has since been dropped.
+    fn notify_observers(&self, event: ProviderEvent) {
+        let mut observers = self.observers.lock().unwrap();
+        observers.retain(|weak| {
+            if let Some(observer) = weak.upgrade() {
+                observer.notify(event.clone());
+                true
+            } else {
+                false
+            }
+        });
     }
 
     /// Returns all providers
@@ -25,6 +146,16 @@ impl ProviderManager {
         &self.providers
     }
 
+    /// Returns the maximum number of providers that may be selected at once
+    pub fn max_selected(&self) -> usize {
+        self.max_selected
+    }
+
+    /// Returns the registry entry for `provider_id`, if configured
+    pub fn get_provider(&self, provider_id: ProviderId) -> Option<&Provider> {
+        self.providers.iter().find(|p| p.id == provider_id)
+    }
+
     /// Updates the selection state of a provider
     /// Returns an error if attempting to deselect the last selected provider
     pub fn update_provider_selection(
@@ -32,6 +163,8 @@ impl ProviderManager {
         provider_id: ProviderId,
         is_selected: bool,
     ) -> Result<Provider, CommandError> {
+        let _span = crate::logging::start_span("provider_selection_change");
+
         // Validation: Cannot deselect last provider (FR-004)
         if !is_selected {
             let selected_count = self.selected_count();
@@ -42,13 +175,14 @@ impl ProviderManager {
             }
         }
 
-        // Validation: Cannot select more than 3 providers (TC-005)
+        // Validation: Cannot select more than max_selected providers (TC-005)
         if is_selected {
             let selected_count = self.selected_count();
-            if selected_count >= 3 {
-                return Err(CommandError::validation(
-                    "Maximum 3 providers can be selected",
-                ));
+            if selected_count >= self.max_selected {
+                return Err(CommandError::validation(format!(
+                    "Maximum {} providers can be selected",
+                    self.max_selected
+                )));
             }
         }
 
@@ -62,7 +196,16 @@ impl ProviderManager {
             })?;
 
         provider.is_selected = is_selected;
-        Ok(provider.clone())
+        let provider = provider.clone();
+
+        // Emitted only after validation passed and state is committed, so
+        // observers never see a selection change that was later rejected.
+        self.notify_observers(ProviderEvent::SelectionChanged {
+            provider_id: provider.id,
+            is_selected: provider.is_selected,
+        });
+
+        Ok(provider)
     }
 
     /// Returns the number of currently selected providers
@@ -85,6 +228,143 @@ impl Default for ProviderManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::Mutex as StdMutex;
+
+    struct RecordingObserver {
+        events: StdMutex<Vec<ProviderEvent>>,
+    }
+
+    impl RecordingObserver {
+        fn new() -> Self {
+            Self {
+                events: StdMutex::new(Vec::new()),
+            }
+        }
+    }
+
+    impl Observer for RecordingObserver {
+        fn notify(&self, event: ProviderEvent) {
+            self.events.lock().unwrap().push(event);
+        }
+    }
+
+    #[test]
+    fn test_subscribe_receives_selection_changed_after_commit() {
+        let mut manager = ProviderManager::new();
+        let recorder = Arc::new(RecordingObserver::new());
+        let observer: Arc<dyn Observer> = recorder.clone();
+        manager.subscribe(&observer);
+
+        manager
+            .update_provider_selection(ProviderId::ChatGPT, false)
+            .expect("Should allow deselecting");
+
+        let events = recorder.events.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            ProviderEvent::SelectionChanged {
+                provider_id,
+                is_selected,
+            } => {
+                assert_eq!(*provider_id, ProviderId::ChatGPT);
+                assert!(!is_selected);
+            }
+            other => panic!("Expected SelectionChanged, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_rejected_selection_change_does_not_notify() {
+        let mut manager = ProviderManager::new();
+        let recorder = Arc::new(RecordingObserver::new());
+        let observer: Arc<dyn Observer> = recorder.clone();
+        manager.subscribe(&observer);
+
+        manager
+            .update_provider_selection(ProviderId::ChatGPT, false)
+            .unwrap();
+        manager
+            .update_provider_selection(ProviderId::Gemini, false)
+            .unwrap();
+        // This one is rejected by the min-1 validation, so it must not notify.
+        let _ = manager.update_provider_selection(ProviderId::Claude, false);
+
+        let events = recorder.events.lock().unwrap();
+        assert_eq!(events.len(), 2);
+    }
+
+    #[test]
+    fn test_dropped_observer_is_pruned_on_next_notify() {
+        let mut manager = ProviderManager::new();
+        {
+            let observer: Arc<dyn Observer> = Arc::new(RecordingObserver::new());
+            manager.subscribe(&observer);
+        } // observer's only Arc drops here
+
+        manager
+            .update_provider_selection(ProviderId::ChatGPT, false)
+            .unwrap();
+
+        assert_eq!(manager.observers.lock().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_unsubscribe_stops_future_notifications() {
+        let mut manager = ProviderManager::new();
+        let recorder = Arc::new(RecordingObserver::new());
+        let observer: Arc<dyn Observer> = recorder.clone();
+        manager.subscribe(&observer);
+        manager.unsubscribe(&observer);
+
+        manager
+            .update_provider_selection(ProviderId::ChatGPT, false)
+            .unwrap();
+
+        assert_eq!(recorder.events.lock().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_from_registry_rejects_empty_registry() {
+        let result = ProviderManager::from_registry(vec![]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_from_registry_rejects_duplicate_ids() {
+        let result = ProviderManager::from_registry(vec![
+            Provider::new(ProviderId::ChatGPT, vec![".a".to_string()]),
+            Provider::new(ProviderId::ChatGPT, vec![".b".to_string()]),
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_from_registry_rejects_empty_url() {
+        let mut provider = Provider::new(ProviderId::ChatGPT, vec![".a".to_string()]);
+        provider.url = String::new();
+        let result = ProviderManager::from_registry(vec![provider]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_from_registry_rejects_empty_auth_selectors() {
+        let result = ProviderManager::from_registry(vec![Provider::new(
+            ProviderId::ChatGPT,
+            vec![],
+        )]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_from_registry_max_selected_defaults_to_entry_count() {
+        let manager = ProviderManager::from_registry(vec![
+            Provider::new(ProviderId::ChatGPT, vec![".a".to_string()]),
+            Provider::new(ProviderId::Gemini, vec![".b".to_string()]),
+        ])
+        .unwrap();
+
+        assert_eq!(manager.max_selected(), 2);
+    }
 
     #[test]
     fn test_new_returns_three_providers() {
@@ -8,6 +8,30 @@ use crate::types::ProviderId;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+/// Event fanned out to observers when provider state changes on its own
+/// (selection toggled, auth status transitions) rather than as the direct
+/// result of the caller's own request, so listeners can react instead of
+/// re-polling `get_selected_providers` / re-running `generate_auth_check_script`.
+#[derive(Debug, Clone)]
+pub enum ProviderEvent {
+    SelectionChanged {
+        provider_id: ProviderId,
+        is_selected: bool,
+    },
+    AuthStatusChanged {
+        provider_id: ProviderId,
+        is_authenticated: bool,
+    },
+}
+
+/// Implemented by anything that wants to react to `ProviderEvent`s without
+/// polling. `ProviderManager`/`WebviewManager` hold subscribers as
+/// `Weak<dyn Observer>`, so a dropped listener is pruned on the next
+/// notification instead of leaking or requiring an explicit unsubscribe.
+pub trait Observer: Send + Sync {
+    fn notify(&self, event: ProviderEvent);
+}
+
 /// Represents an LLM provider with its configuration and state
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Provider {
@@ -17,10 +41,17 @@ pub struct Provider {
     pub is_selected: bool,
     pub is_authenticated: bool,
     pub selector_config_id: String,
+    /// CSS selectors checked (in order) to tell whether this provider's
+    /// webview is currently logged in, consumed by
+    /// `WebviewManager::generate_auth_check_script`
+    pub auth_selectors: Vec<String>,
 }
 
 impl Provider {
-    pub fn new(id: ProviderId) -> Self {
+    /// Builds a provider entry from its registry fields. Prefer
+    /// `manager::default_registry` for the three built-in providers; this is
+    /// the constructor a TOML/JSON-loaded registry entry would feed into.
+    pub fn new(id: ProviderId, auth_selectors: Vec<String>) -> Self {
         Self {
             name: id.as_str().to_string(),
             url: id.url().to_string(),
@@ -28,6 +59,7 @@ impl Provider {
             is_selected: true,
             is_authenticated: false,
             selector_config_id: id.as_str().to_string(),
+            auth_selectors,
         }
     }
 }
@@ -0,0 +1,324 @@
+// Background retry subsystem for stuck submissions
+// Periodically re-injects `Retrying` submissions with exponential backoff,
+// and circuit-breaks providers that keep failing so their pending
+// submissions fail fast instead of being retried blindly.
+
+use crate::injection::capability::CapabilitySet;
+use crate::providers::config::ProviderConfigs;
+use crate::status::tracker::StatusTracker;
+use crate::status::DEFAULT_MAX_ATTEMPTS;
+use crate::types::{ExecutePromptPayload, ProviderId, SubmissionErrorType};
+use crate::webview::manager::WebviewManager;
+use crate::{log_error, log_info};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+/// Base delay before the first retry attempt
+const BASE_DELAY_MS: u64 = 500;
+/// Upper bound on the exponential backoff delay
+const MAX_DELAY_MS: u64 = 16_000;
+/// How often the background loop scans for submissions due to retry
+const SCAN_INTERVAL: Duration = Duration::from_millis(250);
+/// Consecutive transient failures for a provider before it is treated as
+/// unavailable and its pending submissions are failed fast
+const UNAVAILABLE_THRESHOLD: u8 = 3;
+/// How long a provider stays marked unavailable before being given another chance
+const UNAVAILABLE_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// Computes the exponential backoff delay for a given attempt count:
+/// `base_delay * 2^(attempt_count - 1)`, capped at `MAX_DELAY_MS`, with up to
+/// 10% random jitter so multiple stuck submissions don't all retry in lockstep
+pub fn backoff_delay(attempt_count: u8) -> Duration {
+    let exponent = attempt_count.saturating_sub(1).min(8) as u32;
+    let capped = BASE_DELAY_MS.saturating_mul(1u64 << exponent).min(MAX_DELAY_MS);
+    let jitter_range = capped / 10 + 1;
+    let jitter = rand::random::<u64>() % jitter_range;
+
+    Duration::from_millis(capped + jitter)
+}
+
+/// Tracks providers that have failed repeatedly so pending submissions can
+/// fail fast instead of being retried against a provider that's clearly down
+struct UnavailableProviders {
+    marked_at: Mutex<HashMap<ProviderId, Instant>>,
+    consecutive_failures: Mutex<HashMap<ProviderId, u8>>,
+}
+
+impl UnavailableProviders {
+    fn new() -> Self {
+        Self {
+            marked_at: Mutex::new(HashMap::new()),
+            consecutive_failures: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Records a transient failure for a provider, marking it unavailable
+    /// once it crosses `UNAVAILABLE_THRESHOLD` consecutive failures
+    fn record_failure(&self, provider_id: ProviderId) {
+        let mut failures = self.consecutive_failures.lock().unwrap();
+        let count = failures.entry(provider_id).or_insert(0);
+        *count += 1;
+
+        if *count >= UNAVAILABLE_THRESHOLD {
+            self.marked_at.lock().unwrap().insert(provider_id, Instant::now());
+        }
+    }
+
+    /// Clears a provider's failure streak after a successful retry
+    fn record_success(&self, provider_id: ProviderId) {
+        self.consecutive_failures.lock().unwrap().remove(&provider_id);
+        self.marked_at.lock().unwrap().remove(&provider_id);
+    }
+
+    /// Returns true if the provider is currently circuit-broken and hasn't cooled down yet
+    fn is_unavailable(&self, provider_id: ProviderId) -> bool {
+        match self.marked_at.lock().unwrap().get(&provider_id) {
+            Some(since) => since.elapsed() < UNAVAILABLE_COOLDOWN,
+            None => false,
+        }
+    }
+}
+
+/// Schedules and executes retries for submissions stuck in `Retrying`
+pub struct RetryScheduler {
+    tracker: Arc<StatusTracker>,
+    provider_configs: Arc<ProviderConfigs>,
+    capabilities: Arc<CapabilitySet>,
+    webview_manager: Arc<WebviewManager>,
+    unavailable: UnavailableProviders,
+    max_attempts: u8,
+    due_at: Mutex<HashMap<String, Instant>>,
+}
+
+impl RetryScheduler {
+    /// Creates a scheduler capped at `status::DEFAULT_MAX_ATTEMPTS`, matching
+    /// the cap `Submission::fail` already applies when deciding to retry
+    pub fn new(
+        tracker: Arc<StatusTracker>,
+        provider_configs: Arc<ProviderConfigs>,
+        capabilities: Arc<CapabilitySet>,
+        webview_manager: Arc<WebviewManager>,
+    ) -> Self {
+        Self {
+            tracker,
+            provider_configs,
+            capabilities,
+            webview_manager,
+            unavailable: UnavailableProviders::new(),
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            due_at: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Scans for `Retrying` submissions whose backoff delay has elapsed and
+    /// re-injects them. Submissions belonging to a circuit-broken provider,
+    /// or that have already exhausted `max_attempts`, are hard-failed instead.
+    async fn scan_and_retry(&self, app: &AppHandle) {
+        let submissions = match self.tracker.retrying_submissions() {
+            Ok(submissions) => submissions,
+            Err(e) => {
+                log_error!("Failed to list retrying submissions", { "error": e.to_string() });
+                return;
+            }
+        };
+
+        for submission in submissions {
+            if submission.attempt_count >= self.max_attempts {
+                let _ = self.tracker.fail_submission(
+                    &submission.id,
+                    submission.error_type.unwrap_or(SubmissionErrorType::Timeout),
+                    format!("Exceeded max attempts ({})", self.max_attempts),
+                );
+                continue;
+            }
+
+            if self.unavailable.is_unavailable(submission.provider_id) {
+                let _ = self.tracker.fail_submission(
+                    &submission.id,
+                    submission.error_type.unwrap_or(SubmissionErrorType::NetworkError),
+                    "Provider marked unavailable after repeated failures".to_string(),
+                );
+                continue;
+            }
+
+            let due = *self
+                .due_at
+                .lock()
+                .unwrap()
+                .entry(submission.id.clone())
+                .or_insert_with(|| Instant::now() + backoff_delay(submission.attempt_count));
+
+            if Instant::now() < due {
+                continue;
+            }
+
+            self.due_at.lock().unwrap().remove(&submission.id);
+
+            let config = match self.provider_configs.get_config(submission.provider_id) {
+                Ok(config) => config,
+                Err(e) => {
+                    log_error!("No provider config for retrying submission", {
+                        "submission_id": &submission.id,
+                        "provider_id": format!("{:?}", submission.provider_id),
+                        "error": e.to_string()
+                    });
+                    continue;
+                }
+            };
+
+            let injector = match crate::injection::injector::Injector::new() {
+                Ok(injector) => injector,
+                Err(e) => {
+                    log_error!("Failed to initialize injector for retry", {
+                        "submission_id": &submission.id,
+                        "error": e.to_string()
+                    });
+                    continue;
+                }
+            };
+            let script = injector.prepare_injection_with_args(
+                &config.input_selectors,
+                &config.submit_selectors,
+                &serde_json::json!({ "prompt": submission.prompt_content }),
+                &submission.id,
+                submission.provider_id,
+            );
+
+            let capability = match self.capabilities.get(submission.provider_id) {
+                Some(capability) => capability.clone(),
+                None => {
+                    log_error!("No capability granted for retrying submission's provider", {
+                        "submission_id": &submission.id,
+                        "provider_id": format!("{:?}", submission.provider_id)
+                    });
+                    continue;
+                }
+            };
+            let payload = ExecutePromptPayload {
+                submission_id: submission.id.clone(),
+                provider_id: submission.provider_id,
+                script: script.clone(),
+                capability,
+            };
+            if let Err(e) = injector.authorize(&payload) {
+                log_error!("Retry script rejected by capability check", {
+                    "submission_id": &submission.id,
+                    "error": e.to_string()
+                });
+                let _ = self.tracker.fail_submission(
+                    &submission.id,
+                    SubmissionErrorType::InjectionFailed,
+                    e.to_string(),
+                );
+                continue;
+            }
+
+            if let Err(e) = self.tracker.start_submission(&submission.id) {
+                log_error!("Failed to restart submission for retry", {
+                    "submission_id": &submission.id,
+                    "error": e.to_string()
+                });
+                continue;
+            }
+
+            // Dispatch via `Injector::execute`, which times out the eval,
+            // isolates the script's scope, and rounds its real result back
+            // through `WebviewManager::execute_script` rather than firing
+            // the script blind via a bare `webview.eval`.
+            match injector
+                .execute(app, &self.webview_manager, submission.provider_id, &script)
+                .await
+            {
+                Ok(result) if result.success => {
+                    // Dispatch succeeding only means the script was handed to the
+                    // webview, not that the provider finished responding -- the
+                    // submission stays `InProgress` until a real completion signal
+                    // (response harvesting, or `check_timeouts` if none ever arrives)
+                    // moves it out of that state.
+                    log_info!("Retry re-injected successfully", {
+                        "submission_id": &submission.id,
+                        "provider_id": format!("{:?}", submission.provider_id)
+                    });
+                    self.unavailable.record_success(submission.provider_id);
+                }
+                Ok(result) => {
+                    self.unavailable.record_failure(submission.provider_id);
+                    let message = result
+                        .error_message
+                        .unwrap_or_else(|| "Retry script reported failure".to_string());
+                    let _ = self.tracker.fail_submission(
+                        &submission.id,
+                        SubmissionErrorType::InjectionFailed,
+                        message,
+                    );
+                }
+                Err(e) => {
+                    self.unavailable.record_failure(submission.provider_id);
+                    let _ = self.tracker.fail_submission(
+                        &submission.id,
+                        SubmissionErrorType::InjectionFailed,
+                        e,
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Spawns the background retry loop, holding an `Arc` to the `StatusTracker`
+/// and provider configs, scanning every `SCAN_INTERVAL` for submissions whose
+/// backoff delay has elapsed and re-injecting them
+pub fn spawn_retry_loop(app: AppHandle, scheduler: Arc<RetryScheduler>) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(SCAN_INTERVAL).await;
+            scheduler.scan_and_retry(&app).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_delay_doubles_and_caps() {
+        let first = backoff_delay(1).as_millis();
+        let second = backoff_delay(2).as_millis();
+        let capped = backoff_delay(20).as_millis();
+
+        assert!(first >= BASE_DELAY_MS as u128);
+        assert!(first < BASE_DELAY_MS as u128 + BASE_DELAY_MS as u128 / 10 + 1);
+        assert!(second >= (BASE_DELAY_MS * 2) as u128);
+        assert!(capped >= MAX_DELAY_MS as u128);
+        assert!(capped < MAX_DELAY_MS as u128 + MAX_DELAY_MS as u128 / 10 + 1);
+    }
+
+    #[test]
+    fn test_unavailable_providers_trips_after_threshold() {
+        let unavailable = UnavailableProviders::new();
+
+        for _ in 0..UNAVAILABLE_THRESHOLD {
+            assert!(!unavailable.is_unavailable(ProviderId::ChatGPT));
+            unavailable.record_failure(ProviderId::ChatGPT);
+        }
+
+        assert!(unavailable.is_unavailable(ProviderId::ChatGPT));
+        assert!(!unavailable.is_unavailable(ProviderId::Gemini));
+    }
+
+    #[test]
+    fn test_unavailable_providers_clears_on_success() {
+        let unavailable = UnavailableProviders::new();
+
+        for _ in 0..UNAVAILABLE_THRESHOLD {
+            unavailable.record_failure(ProviderId::Claude);
+        }
+        assert!(unavailable.is_unavailable(ProviderId::Claude));
+
+        unavailable.record_success(ProviderId::Claude);
+        assert!(!unavailable.is_unavailable(ProviderId::Claude));
+    }
+}
@@ -0,0 +1,271 @@
+// Sieve-style prompt routing and rewriting rule engine
+// Transforms or gates a prompt per provider before `script_builder` runs,
+// modeled on the Sieve mail-filter condition/action model
+
+use crate::types::{CommandError, ProviderId};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// A condition that gates a rule's actions
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type")]
+pub enum Condition {
+    Provider(ProviderId),
+    PromptContains(String),
+    PromptMatches(String),
+    PromptLongerThan(usize),
+}
+
+impl Condition {
+    /// Returns true if this condition matches the given provider/prompt
+    pub fn matches(&self, provider_id: ProviderId, prompt: &str) -> bool {
+        match self {
+            Condition::Provider(id) => *id == provider_id,
+            Condition::PromptContains(needle) => prompt.contains(needle.as_str()),
+            Condition::PromptMatches(pattern) => regex::Regex::new(pattern)
+                .map(|re| re.is_match(prompt))
+                .unwrap_or(false),
+            Condition::PromptLongerThan(len) => prompt.len() > *len,
+        }
+    }
+}
+
+/// An action a matching rule performs on the working prompt
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type")]
+pub enum Action {
+    PrependText(String),
+    AppendText(String),
+    Replace { from: String, to: String },
+    SkipProvider,
+    Stop,
+}
+
+/// A single ordered rule: one condition gating one or more actions
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Rule {
+    pub name: String,
+    pub condition: Condition,
+    pub actions: Vec<Action>,
+}
+
+/// An ordered, user-editable list of prompt routing/rewriting rules
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RuleSet {
+    pub version: String,
+    pub rules: Vec<Rule>,
+}
+
+/// Outcome of evaluating a ruleset for one (provider, prompt) pair
+#[derive(Debug, Clone, PartialEq)]
+pub enum EvalOutcome {
+    /// The prompt should be sent, possibly rewritten
+    Rewritten(String),
+    /// `SkipProvider` fired; this provider should not receive the prompt
+    Skip,
+}
+
+impl RuleSet {
+    /// Loads a ruleset from a user-editable config file and validates it,
+    /// the same way `ProviderConfigs::load` validates its config
+    pub fn load(path: &std::path::Path) -> Result<Self, CommandError> {
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            CommandError::internal(format!("Failed to read ruleset at {:?}: {}", path, e))
+        })?;
+
+        let ruleset: RuleSet = serde_json::from_str(&contents)
+            .map_err(|e| CommandError::internal(format!("Failed to parse ruleset: {}", e)))?;
+
+        ruleset.validate()?;
+
+        Ok(ruleset)
+    }
+
+    /// Validates rule names, actions, and regex conditions
+    fn validate(&self) -> Result<(), CommandError> {
+        for rule in &self.rules {
+            if rule.name.is_empty() {
+                return Err(CommandError::validation("Rule name cannot be empty"));
+            }
+
+            if rule.actions.is_empty() {
+                return Err(CommandError::validation(format!(
+                    "Rule '{}' must have at least one action",
+                    rule.name
+                )));
+            }
+
+            if let Condition::PromptMatches(pattern) = &rule.condition {
+                if regex::Regex::new(pattern).is_err() {
+                    return Err(CommandError::validation(format!(
+                        "Rule '{}' has an invalid regex: {}",
+                        rule.name, pattern
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Evaluates the ruleset for a given provider/prompt: applies every
+    /// matching rule's actions in order against the working prompt,
+    /// short-circuiting on `Stop` and returning early if `SkipProvider` fires
+    pub fn evaluate(&self, provider_id: ProviderId, prompt: &str) -> EvalOutcome {
+        let mut working = prompt.to_string();
+
+        for rule in &self.rules {
+            if !rule.condition.matches(provider_id, &working) {
+                continue;
+            }
+
+            for action in &rule.actions {
+                match action {
+                    Action::PrependText(text) => working = format!("{}{}", text, working),
+                    Action::AppendText(text) => working = format!("{}{}", working, text),
+                    Action::Replace { from, to } => {
+                        working = working.replace(from.as_str(), to.as_str())
+                    }
+                    Action::SkipProvider => return EvalOutcome::Skip,
+                    Action::Stop => return EvalOutcome::Rewritten(working),
+                }
+            }
+        }
+
+        EvalOutcome::Rewritten(working)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(name: &str, condition: Condition, actions: Vec<Action>) -> Rule {
+        Rule {
+            name: name.to_string(),
+            condition,
+            actions,
+        }
+    }
+
+    #[test]
+    fn test_evaluate_prepends_only_for_matching_provider() {
+        let ruleset = RuleSet {
+            version: "1.0.0".to_string(),
+            rules: vec![rule(
+                "claude-preamble",
+                Condition::Provider(ProviderId::Claude),
+                vec![Action::PrependText("System: be concise.\n".to_string())],
+            )],
+        };
+
+        let claude_result = ruleset.evaluate(ProviderId::Claude, "Hello");
+        assert_eq!(
+            claude_result,
+            EvalOutcome::Rewritten("System: be concise.\nHello".to_string())
+        );
+
+        let chatgpt_result = ruleset.evaluate(ProviderId::ChatGPT, "Hello");
+        assert_eq!(chatgpt_result, EvalOutcome::Rewritten("Hello".to_string()));
+    }
+
+    #[test]
+    fn test_evaluate_skip_provider_halts_evaluation() {
+        let ruleset = RuleSet {
+            version: "1.0.0".to_string(),
+            rules: vec![
+                rule(
+                    "skip-gemini",
+                    Condition::Provider(ProviderId::Gemini),
+                    vec![Action::SkipProvider],
+                ),
+                rule(
+                    "append-footer",
+                    Condition::PromptContains("Hello".to_string()),
+                    vec![Action::AppendText(" (footer)".to_string())],
+                ),
+            ],
+        };
+
+        assert_eq!(
+            ruleset.evaluate(ProviderId::Gemini, "Hello"),
+            EvalOutcome::Skip
+        );
+        assert_eq!(
+            ruleset.evaluate(ProviderId::ChatGPT, "Hello"),
+            EvalOutcome::Rewritten("Hello (footer)".to_string())
+        );
+    }
+
+    #[test]
+    fn test_evaluate_stop_prevents_later_rules_from_running() {
+        let ruleset = RuleSet {
+            version: "1.0.0".to_string(),
+            rules: vec![
+                rule(
+                    "stop-early",
+                    Condition::PromptContains("stop".to_string()),
+                    vec![Action::Stop],
+                ),
+                rule(
+                    "should-not-run",
+                    Condition::PromptContains("stop".to_string()),
+                    vec![Action::AppendText(" (unreachable)".to_string())],
+                ),
+            ],
+        };
+
+        assert_eq!(
+            ruleset.evaluate(ProviderId::ChatGPT, "please stop"),
+            EvalOutcome::Rewritten("please stop".to_string())
+        );
+    }
+
+    #[test]
+    fn test_evaluate_replace_action() {
+        let ruleset = RuleSet {
+            version: "1.0.0".to_string(),
+            rules: vec![rule(
+                "strip-boilerplate",
+                Condition::PromptContains("Thanks in advance".to_string()),
+                vec![Action::Replace {
+                    from: "Thanks in advance".to_string(),
+                    to: String::new(),
+                }],
+            )],
+        };
+
+        assert_eq!(
+            ruleset.evaluate(ProviderId::ChatGPT, "Help me. Thanks in advance"),
+            EvalOutcome::Rewritten("Help me. ".to_string())
+        );
+    }
+
+    #[test]
+    fn test_validate_rejects_invalid_regex() {
+        let ruleset = RuleSet {
+            version: "1.0.0".to_string(),
+            rules: vec![rule(
+                "bad-regex",
+                Condition::PromptMatches("(".to_string()),
+                vec![Action::Stop],
+            )],
+        };
+
+        assert!(ruleset.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_rejects_rule_with_no_actions() {
+        let ruleset = RuleSet {
+            version: "1.0.0".to_string(),
+            rules: vec![rule(
+                "no-actions",
+                Condition::PromptLongerThan(10),
+                vec![],
+            )],
+        };
+
+        assert!(ruleset.validate().is_err());
+    }
+}
@@ -1,27 +1,51 @@
+use crate::campaign::CampaignManager;
+use crate::history::HistoryStore;
+use crate::injection::capability::CapabilitySet;
 use crate::providers::config::ProviderConfigs;
 use crate::providers::manager::ProviderManager;
+use crate::rules::RuleSet;
 use crate::status::tracker::StatusTracker;
+use crate::webview::manager::WebviewManager;
 use log::{info, warn};
 use std::sync::{Arc, Mutex};
 
 /// Application state shared across Tauri commands
 /// This state is managed by Tauri and accessible to all commands
 pub struct AppState {
-    /// Provider manager for handling provider selection and configuration
-    pub provider_manager: Mutex<ProviderManager>,
-    /// Provider selector configurations (CSS selectors, etc.)
-    pub provider_configs: Option<ProviderConfigs>,
+    /// Provider manager for handling provider selection and configuration.
+    /// `Arc`-wrapped so the auth polling loop (`webview::manager::spawn_auth_polling_loop`)
+    /// can hold its own handle without borrowing from `AppState`.
+    pub provider_manager: Arc<Mutex<ProviderManager>>,
+    /// Provider selector configurations (CSS selectors, etc.), layered from
+    /// the embedded defaults with an optional user override merged on top
+    pub provider_configs: Mutex<Option<ProviderConfigs>>,
     /// Status tracker for managing prompt submissions
     pub status_tracker: Arc<StatusTracker>,
+    /// User-editable prompt routing/rewriting rules (absent if no ruleset
+    /// file exists, in which case prompts pass through unmodified)
+    pub rules: Option<RuleSet>,
+    /// Capability allowlist gating which provider origin a script may target
+    pub capabilities: CapabilitySet,
+    /// Persisted prompt/response history, grouped by submit_prompt batch
+    pub history: Arc<HistoryStore>,
+    /// Webview manager backing `Injector::execute`'s timeout-wrapped,
+    /// isolated-scope dispatch, whose script executions are fulfilled via
+    /// `report_script_result`
+    pub webview_manager: Arc<WebviewManager>,
+    /// Groups one prompt fanned out to several providers into a single
+    /// trackable campaign, on top of the same `StatusTracker`
+    pub campaign_manager: Arc<CampaignManager>,
 }
 
 impl AppState {
     pub fn new() -> Self {
-        // Try to load provider configs
-        let provider_configs = match ProviderConfigs::load() {
-            Ok(configs) => {
+        // Try to load provider configs, merging any user override on top of
+        // the embedded defaults
+        let provider_configs = match ProviderConfigs::load_layered(&Self::user_provider_config_path())
+        {
+            Ok(layered) => {
                 info!("Successfully loaded provider configurations");
-                Some(configs)
+                Some(layered.value)
             }
             Err(e) => {
                 warn!("Failed to load provider configurations: {}", e);
@@ -29,12 +53,94 @@ impl AppState {
             }
         };
 
+        let rules = match Self::rules_path() {
+            Some(path) if path.exists() => match RuleSet::load(&path) {
+                Ok(ruleset) => {
+                    info!("Successfully loaded prompt rules from {:?}", path);
+                    Some(ruleset)
+                }
+                Err(e) => {
+                    warn!("Failed to load prompt rules from {:?}: {}", path, e);
+                    None
+                }
+            },
+            _ => None,
+        };
+
+        let status_tracker = Arc::new(StatusTracker::new());
+
+        // Build the registry from the loaded provider configs when available,
+        // so its selection state and auth-check selectors track
+        // config/providers.json (or a user override) instead of the
+        // hardcoded defaults; fall back to the built-in registry otherwise,
+        // the same degrade-gracefully pattern used for `rules`. `ProviderId`
+        // is a closed enum, so this still can't add a provider beyond the
+        // fixed ChatGPT/Gemini/Claude set.
+        let provider_manager = provider_configs
+            .as_ref()
+            .and_then(|configs| match ProviderManager::from_provider_configs(configs) {
+                Ok(manager) => Some(manager),
+                Err(e) => {
+                    warn!("Failed to build provider registry from config, falling back to built-in registry: {}", e);
+                    None
+                }
+            })
+            .unwrap_or_else(ProviderManager::new);
+
+        // Grant capabilities for whichever providers the registry above
+        // actually ended up with, rather than a hardcoded 3-provider literal,
+        // so this stays correct even though it's redundant today (the
+        // registry can only ever report ChatGPT/Gemini/Claude).
+        let provider_ids: Vec<_> = provider_manager
+            .get_all_providers()
+            .iter()
+            .map(|provider| provider.id)
+            .collect();
+        let capabilities = CapabilitySet::default_for_providers(&provider_ids);
+
         Self {
-            provider_manager: Mutex::new(ProviderManager::new()),
-            provider_configs,
-            status_tracker: Arc::new(StatusTracker::new()),
+            provider_manager: Arc::new(Mutex::new(provider_manager)),
+            provider_configs: Mutex::new(provider_configs),
+            campaign_manager: Arc::new(CampaignManager::new(Arc::clone(&status_tracker))),
+            status_tracker,
+            rules,
+            capabilities,
+            history: Arc::new(HistoryStore::new(Self::history_path())),
+            webview_manager: Arc::new(WebviewManager::new()),
         }
     }
+
+    /// Location of the persisted submission history file
+    fn history_path() -> std::path::PathBuf {
+        dirs::config_dir()
+            .map(|dir| dir.join("chenchen").join("history.json"))
+            .unwrap_or_default()
+    }
+
+    /// Location of the user-editable provider selector overrides
+    fn user_provider_config_path() -> std::path::PathBuf {
+        dirs::config_dir()
+            .map(|dir| dir.join("chenchen").join("providers.json"))
+            .unwrap_or_default()
+    }
+
+    /// Location of the user-editable ruleset file, if a config directory is resolvable
+    fn rules_path() -> Option<std::path::PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("chenchen").join("rules.json"))
+    }
+
+    /// Re-reads the embedded defaults and re-merges the user override on top,
+    /// so a broken selector can be patched live without restarting the app
+    pub fn reload_provider_configs(&self) -> Result<(), crate::types::CommandError> {
+        let layered = ProviderConfigs::load_layered(&Self::user_provider_config_path())?;
+
+        let mut configs = self.provider_configs.lock().map_err(|e| {
+            crate::types::CommandError::internal(format!("Failed to acquire lock: {}", e))
+        })?;
+        *configs = Some(layered.value);
+
+        Ok(())
+    }
 }
 
 impl Default for AppState {
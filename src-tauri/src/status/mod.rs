@@ -6,6 +6,17 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+/// Default maximum number of attempts a retryable failure gets before giving
+/// up and transitioning to `Failed` instead of `Retrying`. Shared with the
+/// background retry subsystem so both layers agree on the cap.
+pub const DEFAULT_MAX_ATTEMPTS: u8 = 5;
+
+/// Default number of seconds an `InProgress` submission is allowed to run
+/// before `is_timed_out` considers it stuck. Generous enough to cover a slow
+/// generation plus the response-harvesting script's own polling, rather than
+/// the much shorter round trip a single injection script needs.
+pub const DEFAULT_SUBMISSION_TIMEOUT_SECS: f64 = 120.0;
+
 /// Tracks a prompt submission to a specific provider
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Submission {
@@ -18,6 +29,17 @@ pub struct Submission {
     pub error_message: Option<String>,
     pub started_at: Option<String>,
     pub completed_at: Option<String>,
+    /// Harvested response text from the provider, once captured by the
+    /// response-harvesting script. Absent until a `report_response` arrives.
+    pub response_text: Option<String>,
+    /// Id of the `submit_prompt` batch this submission belongs to, so the
+    /// history subsystem can group per-provider results together. Absent for
+    /// submissions created outside a batch (e.g. directly in tests).
+    pub batch_id: Option<String>,
+    /// Path to a PNG snapshot of the provider's page taken at the moment
+    /// this submission failed, for diagnosing selector drift. Absent until
+    /// `StatusTracker::record_failure_screenshot` stores one.
+    pub failure_screenshot: Option<std::path::PathBuf>,
 }
 
 impl Submission {
@@ -33,6 +55,9 @@ impl Submission {
             error_message: None,
             started_at: None,
             completed_at: None,
+            response_text: None,
+            batch_id: None,
+            failure_screenshot: None,
         }
     }
 
@@ -74,7 +99,7 @@ impl Submission {
         }
 
         // Check if error type should trigger retry
-        if error_type.should_retry() && self.attempt_count < 2 {
+        if error_type.should_retry() && self.attempt_count < DEFAULT_MAX_ATTEMPTS {
             // Transition to Retrying
             self.status = SubmissionStatus::Retrying;
             self.error_type = Some(error_type);
@@ -90,11 +115,19 @@ impl Submission {
         }
     }
 
-    /// Checks if submission has exceeded timeout (30 seconds)
+    /// Stores the harvested response text captured by the response-harvesting
+    /// script. Can arrive at any point after the submission has started,
+    /// independent of the injection's own success/failure transition.
+    pub fn record_response(&mut self, response_text: String) {
+        self.response_text = Some(response_text);
+    }
+
+    /// Checks if submission has run past `DEFAULT_SUBMISSION_TIMEOUT_SECS`
+    /// since it started
     pub fn is_timed_out(&self) -> bool {
         if let Some(started_at) = &self.started_at {
             if let Ok(elapsed) = time_since(started_at) {
-                return elapsed > 30.0; // 30 second timeout
+                return elapsed > DEFAULT_SUBMISSION_TIMEOUT_SECS;
             }
         }
         false
@@ -171,6 +204,19 @@ mod tests {
         assert_eq!(submission.error_type, Some(SubmissionErrorType::Timeout));
     }
 
+    #[test]
+    fn test_submission_record_response() {
+        let mut submission = Submission::new(ProviderId::ChatGPT, "Test".to_string());
+
+        submission.start().unwrap();
+        submission.record_response("Here is the answer".to_string());
+
+        assert_eq!(
+            submission.response_text,
+            Some("Here is the answer".to_string())
+        );
+    }
+
     #[test]
     fn test_submission_fail_no_retry() {
         let mut submission = Submission::new(ProviderId::ChatGPT, "Test".to_string());
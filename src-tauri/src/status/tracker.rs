@@ -1,30 +1,60 @@
 // StatusTracker for managing submission states and emitting events
 
 use super::Submission;
-use crate::types::{CommandError, ProviderId, SubmissionErrorType};
+use crate::types::{CommandError, ProviderId, SubmissionErrorType, SubmissionEvent, SubmissionStatus};
 use std::collections::HashMap;
 use std::sync::Mutex;
+use tokio::sync::broadcast;
+
+/// Capacity of the submission-event broadcast channel. Slow or absent
+/// subscribers simply miss the oldest events rather than blocking senders.
+const EVENT_CHANNEL_CAPACITY: usize = 256;
+/// How often the background timeout loop scans for stuck `InProgress`
+/// submissions, well below `Submission::is_timed_out`'s
+/// `DEFAULT_SUBMISSION_TIMEOUT_SECS` threshold so a timeout is caught
+/// promptly rather than sitting for another scan
+const TIMEOUT_SCAN_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
 
 /// Manages submission state and emits status change events
 pub struct StatusTracker {
     submissions: Mutex<HashMap<String, Submission>>,
+    events: broadcast::Sender<SubmissionEvent>,
 }
 
 impl StatusTracker {
     /// Creates a new StatusTracker
     pub fn new() -> Self {
+        let (events, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
         Self {
             submissions: Mutex::new(HashMap::new()),
+            events,
         }
     }
 
+    /// Subscribes to the submission-lifecycle event stream
+    pub fn subscribe(&self) -> broadcast::Receiver<SubmissionEvent> {
+        self.events.subscribe()
+    }
+
     /// Creates a new submission and stores it
     pub fn create_submission(
         &self,
         provider_id: ProviderId,
         prompt_content: String,
     ) -> Result<Submission, CommandError> {
-        let submission = Submission::new(provider_id, prompt_content);
+        self.create_submission_in_batch(provider_id, prompt_content, None)
+    }
+
+    /// Creates a new submission tagged with the `submit_prompt` batch it
+    /// belongs to, so the history subsystem can group per-provider results
+    pub fn create_submission_in_batch(
+        &self,
+        provider_id: ProviderId,
+        prompt_content: String,
+        batch_id: Option<String>,
+    ) -> Result<Submission, CommandError> {
+        let mut submission = Submission::new(provider_id, prompt_content);
+        submission.batch_id = batch_id;
         let id = submission.id.clone();
 
         let mut submissions = self.submissions.lock().map_err(|e| {
@@ -33,9 +63,48 @@ impl StatusTracker {
 
         submissions.insert(id, submission.clone());
 
+        self.emit_updated(&submission);
+
         Ok(submission)
     }
 
+    /// Announces the fan-out plan for a batch of submissions (one `Plan` event
+    /// per `submit_prompt` call), before any provider has started
+    pub fn emit_plan(&self, submission_id: &str, providers: Vec<ProviderId>) {
+        let _ = self.events.send(SubmissionEvent::Plan {
+            submission_id: submission_id.to_string(),
+            providers,
+        });
+    }
+
+    /// Broadcasts the full current state of a submission, fired on every
+    /// transition so subscribers can replace their local copy instead of
+    /// polling `get_status`
+    fn emit_updated(&self, submission: &Submission) {
+        let _ = self.events.send(SubmissionEvent::Updated {
+            submission: submission.clone(),
+        });
+    }
+
+    /// Computes the elapsed time between `started_at` and `completed_at`,
+    /// returning 0 if either timestamp is missing or unparseable
+    fn duration_ms(submission: &Submission) -> u64 {
+        let (Some(started), Some(completed)) =
+            (&submission.started_at, &submission.completed_at)
+        else {
+            return 0;
+        };
+
+        let (Ok(start), Ok(end)) = (
+            chrono::DateTime::parse_from_rfc3339(started),
+            chrono::DateTime::parse_from_rfc3339(completed),
+        ) else {
+            return 0;
+        };
+
+        (end - start).num_milliseconds().max(0) as u64
+    }
+
     /// Gets the current status of a submission
     pub fn get_status(&self, submission_id: &str) -> Result<Submission, CommandError> {
         let submissions = self.submissions.lock().map_err(|e| {
@@ -50,9 +119,12 @@ impl StatusTracker {
             })
     }
 
-    /// Updates submission status and emits event
-    ///
-    /// Note: Event emission will be implemented when we integrate with Tauri's event system
+    /// Updates submission status, then broadcasts the resulting state as an
+    /// `Updated` event so subscribers can replace their local copy of the
+    /// submission instead of polling `get_status`. Every status mutator
+    /// below (`start_submission`, `succeed_submission`, `fail_submission`,
+    /// `record_response`) goes through here, so this is the single place
+    /// that needs to emit on every transition.
     pub fn update_status(
         &self,
         submission_id: &str,
@@ -72,20 +144,37 @@ impl StatusTracker {
             CommandError::internal(format!("Failed to update submission: {}", e))
         })?;
 
-        // TODO: Emit submission_status_changed event
-        // app.emit_all("submission_status_changed", submission.clone())?;
+        let submission = submission.clone();
+        self.emit_updated(&submission);
 
-        Ok(submission.clone())
+        Ok(submission)
     }
 
     /// Starts a submission (Pending → InProgress)
     pub fn start_submission(&self, submission_id: &str) -> Result<Submission, CommandError> {
-        self.update_status(submission_id, |s| s.start())
+        let submission = self.update_status(submission_id, |s| s.start())?;
+
+        let _ = self.events.send(SubmissionEvent::Started {
+            submission_id: submission.id.clone(),
+            provider_id: submission.provider_id,
+        });
+
+        Ok(submission)
     }
 
     /// Marks a submission as successful
     pub fn succeed_submission(&self, submission_id: &str) -> Result<Submission, CommandError> {
-        self.update_status(submission_id, |s| s.succeed())
+        let submission = self.update_status(submission_id, |s| s.succeed())?;
+
+        let _ = self.events.send(SubmissionEvent::Finished {
+            submission_id: submission.id.clone(),
+            provider_id: submission.provider_id,
+            status: submission.status,
+            duration_ms: Self::duration_ms(&submission),
+            error_type: None,
+        });
+
+        Ok(submission)
     }
 
     /// Marks a submission as failed (with retry logic)
@@ -95,25 +184,135 @@ impl StatusTracker {
         error_type: SubmissionErrorType,
         error_message: String,
     ) -> Result<Submission, CommandError> {
-        self.update_status(submission_id, |s| s.fail(error_type, error_message))
+        let submission = self.update_status(submission_id, |s| s.fail(error_type, error_message))?;
+
+        match submission.status {
+            SubmissionStatus::Retrying => {
+                let _ = self.events.send(SubmissionEvent::Retrying {
+                    submission_id: submission.id.clone(),
+                    provider_id: submission.provider_id,
+                    attempt: submission.attempt_count,
+                });
+            }
+            SubmissionStatus::Failed => {
+                let _ = self.events.send(SubmissionEvent::Finished {
+                    submission_id: submission.id.clone(),
+                    provider_id: submission.provider_id,
+                    status: submission.status,
+                    duration_ms: Self::duration_ms(&submission),
+                    error_type: submission.error_type,
+                });
+            }
+            _ => {}
+        }
+
+        Ok(submission)
+    }
+
+    /// Records the harvested response text for a submission, once the
+    /// response-harvesting script reports the provider's generation finished
+    pub fn record_response(
+        &self,
+        submission_id: &str,
+        response_text: String,
+    ) -> Result<Submission, CommandError> {
+        let submission = self.update_status(submission_id, |s| {
+            s.record_response(response_text);
+            Ok(())
+        })?;
+
+        Ok(submission)
+    }
+
+    /// Records where a failure screenshot for this submission was saved,
+    /// once `WebviewManager::capture_screenshot` has written one to disk
+    pub fn record_failure_screenshot(
+        &self,
+        submission_id: &str,
+        path: std::path::PathBuf,
+    ) -> Result<Submission, CommandError> {
+        self.update_status(submission_id, |s| {
+            s.failure_screenshot = Some(path);
+            Ok(())
+        })
     }
 
-    /// Checks all in-progress submissions for timeouts
+    /// Checks all in-progress submissions for timeouts, transitioning each
+    /// one to `Retrying` or `Failed` (via `fail_submission`, same as any
+    /// other injection failure) so the timeout is actually acted on and
+    /// broadcast as an event, rather than just reported back as an id
     pub fn check_timeouts(&self) -> Result<Vec<String>, CommandError> {
-        let mut timed_out = Vec::new();
+        let timed_out_ids: Vec<String> = {
+            let submissions = self.submissions.lock().map_err(|e| {
+                CommandError::internal(format!("Failed to acquire lock: {}", e))
+            })?;
+
+            submissions
+                .iter()
+                .filter(|(_, submission)| submission.is_timed_out())
+                .map(|(id, _)| id.clone())
+                .collect()
+        };
+
+        for id in &timed_out_ids {
+            self.fail_submission(id, SubmissionErrorType::Timeout, "Submission timed out".to_string())?;
+        }
+
+        Ok(timed_out_ids)
+    }
 
+    /// Returns every submission currently sitting in `Retrying`, for the
+    /// background retry loop to scan and act on
+    pub fn retrying_submissions(&self) -> Result<Vec<Submission>, CommandError> {
         let submissions = self.submissions.lock().map_err(|e| {
             CommandError::internal(format!("Failed to acquire lock: {}", e))
         })?;
 
-        for (id, submission) in submissions.iter() {
-            if submission.is_timed_out() {
-                timed_out.push(id.clone());
+        Ok(submissions
+            .values()
+            .filter(|s| s.status == SubmissionStatus::Retrying)
+            .cloned()
+            .collect())
+    }
+}
+
+/// Forwards every `SubmissionEvent` broadcast by a `StatusTracker` to the
+/// frontend as a `submission://event` Tauri event, for the lifetime of the
+/// app. Spawned once from `run()`'s setup hook once an `AppHandle` exists.
+pub fn bridge_events_to_webview(tracker: std::sync::Arc<StatusTracker>, app: tauri::AppHandle) {
+    use tauri::Emitter;
+
+    tauri::async_runtime::spawn(async move {
+        let mut events = tracker.subscribe();
+
+        while let Ok(event) = events.recv().await {
+            if let Err(e) = app.emit("submission://event", &event) {
+                crate::log_error!("Failed to emit submission event", {
+                    "error": e.to_string()
+                });
             }
         }
+    });
+}
 
-        Ok(timed_out)
-    }
+/// Spawns the shared timeout-sweeping task: a single background loop that
+/// wakes every `TIMEOUT_SCAN_INTERVAL` and calls `check_timeouts`, so a
+/// submission that dispatched successfully but never heard back from its
+/// provider (no `report_execution_result`, no retry) still eventually moves
+/// out of `InProgress` instead of hanging forever. Spawned once from
+/// `run()`'s setup hook, alongside `bridge_events_to_webview`.
+pub fn spawn_timeout_loop(tracker: std::sync::Arc<StatusTracker>) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(TIMEOUT_SCAN_INTERVAL).await;
+
+            if let Err(e) = tracker.check_timeouts() {
+                crate::log_error!("Failed to scan for timed-out submissions", {
+                    "error": e.to_string()
+                });
+            }
+        }
+    });
 }
 
 impl Default for StatusTracker {
@@ -137,6 +336,20 @@ mod tests {
         assert_eq!(submission.prompt_content, "Test");
     }
 
+    #[test]
+    fn test_create_submission_in_batch_tags_batch_id() {
+        let tracker = StatusTracker::new();
+        let submission = tracker
+            .create_submission_in_batch(
+                ProviderId::ChatGPT,
+                "Test".to_string(),
+                Some("batch-1".to_string()),
+            )
+            .unwrap();
+
+        assert_eq!(submission.batch_id, Some("batch-1".to_string()));
+    }
+
     #[test]
     fn test_get_status() {
         let tracker = StatusTracker::new();
@@ -190,4 +403,150 @@ mod tests {
 
         assert_eq!(updated.status, crate::types::SubmissionStatus::Retrying);
     }
+
+    #[test]
+    fn test_emit_plan_broadcasts_event() {
+        let tracker = StatusTracker::new();
+        let mut events = tracker.subscribe();
+
+        tracker.emit_plan("batch-1", vec![ProviderId::ChatGPT, ProviderId::Gemini]);
+
+        match events.try_recv().unwrap() {
+            SubmissionEvent::Plan {
+                submission_id,
+                providers,
+            } => {
+                assert_eq!(submission_id, "batch-1");
+                assert_eq!(providers, vec![ProviderId::ChatGPT, ProviderId::Gemini]);
+            }
+            other => panic!("Expected Plan event, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_start_and_succeed_submission_broadcast_events() {
+        let tracker = StatusTracker::new();
+        let mut events = tracker.subscribe();
+        let submission = tracker
+            .create_submission(ProviderId::ChatGPT, "Test".to_string())
+            .unwrap();
+        assert!(matches!(
+            events.try_recv().unwrap(),
+            SubmissionEvent::Updated { .. }
+        ));
+
+        tracker.start_submission(&submission.id).unwrap();
+        assert!(matches!(
+            events.try_recv().unwrap(),
+            SubmissionEvent::Updated { .. }
+        ));
+        assert!(matches!(
+            events.try_recv().unwrap(),
+            SubmissionEvent::Started { .. }
+        ));
+
+        tracker.succeed_submission(&submission.id).unwrap();
+        assert!(matches!(
+            events.try_recv().unwrap(),
+            SubmissionEvent::Updated { .. }
+        ));
+        assert!(matches!(
+            events.try_recv().unwrap(),
+            SubmissionEvent::Finished {
+                status: SubmissionStatus::Success,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_updated_event_carries_full_submission() {
+        let tracker = StatusTracker::new();
+        let mut events = tracker.subscribe();
+
+        let submission = tracker
+            .create_submission(ProviderId::ChatGPT, "Test".to_string())
+            .unwrap();
+
+        match events.try_recv().unwrap() {
+            SubmissionEvent::Updated { submission: updated } => {
+                assert_eq!(updated.id, submission.id);
+                assert_eq!(updated.prompt_content, "Test");
+            }
+            other => panic!("Expected Updated event, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_record_response_stores_harvested_text() {
+        let tracker = StatusTracker::new();
+        let submission = tracker
+            .create_submission(ProviderId::ChatGPT, "Test".to_string())
+            .unwrap();
+
+        tracker.start_submission(&submission.id).unwrap();
+        let updated = tracker
+            .record_response(&submission.id, "The answer is 42".to_string())
+            .unwrap();
+
+        assert_eq!(updated.response_text, Some("The answer is 42".to_string()));
+    }
+
+    #[test]
+    fn test_retrying_submissions_returns_only_retrying() {
+        let tracker = StatusTracker::new();
+        let retrying = tracker
+            .create_submission(ProviderId::ChatGPT, "Test".to_string())
+            .unwrap();
+        let pending = tracker
+            .create_submission(ProviderId::Gemini, "Test".to_string())
+            .unwrap();
+
+        tracker.start_submission(&retrying.id).unwrap();
+        tracker
+            .fail_submission(&retrying.id, SubmissionErrorType::Timeout, "Timed out".to_string())
+            .unwrap();
+
+        let found = tracker.retrying_submissions().unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, retrying.id);
+        assert_ne!(found[0].id, pending.id);
+    }
+
+    #[test]
+    fn test_record_failure_screenshot_stores_path() {
+        let tracker = StatusTracker::new();
+        let submission = tracker
+            .create_submission(ProviderId::ChatGPT, "Test".to_string())
+            .unwrap();
+
+        let path = std::path::PathBuf::from("/tmp/chenchen/screenshots/failed.png");
+        let updated = tracker
+            .record_failure_screenshot(&submission.id, path.clone())
+            .unwrap();
+
+        assert_eq!(updated.failure_screenshot, Some(path));
+    }
+
+    #[test]
+    fn test_check_timeouts_transitions_and_reports_timed_out_submissions() {
+        let tracker = StatusTracker::new();
+        let submission = tracker
+            .create_submission(ProviderId::ChatGPT, "Test".to_string())
+            .unwrap();
+        tracker.start_submission(&submission.id).unwrap();
+
+        {
+            let mut submissions = tracker.submissions.lock().unwrap();
+            submissions.get_mut(&submission.id).unwrap().started_at =
+                Some((chrono::Utc::now() - chrono::Duration::seconds(60)).to_rfc3339());
+        }
+
+        let timed_out = tracker.check_timeouts().unwrap();
+
+        assert_eq!(timed_out, vec![submission.id.clone()]);
+        let updated = tracker.get_status(&submission.id).unwrap();
+        assert_eq!(updated.status, SubmissionStatus::Retrying);
+        assert_eq!(updated.error_type, Some(SubmissionErrorType::Timeout));
+    }
 }
@@ -49,11 +49,16 @@ pub enum SubmissionErrorType {
 }
 
 impl SubmissionErrorType {
-    /// Returns true if this error type should trigger a retry
+    /// Returns true if this error type is transient and worth retrying
+    /// (`Timeout`, `NetworkError`, `InjectionFailed`), as opposed to a
+    /// permanent failure (`AuthenticationError`, `RateLimitError`,
+    /// `ElementNotFound`) that won't be fixed by trying again
     pub fn should_retry(&self) -> bool {
         matches!(
             self,
-            SubmissionErrorType::Timeout | SubmissionErrorType::NetworkError
+            SubmissionErrorType::Timeout
+                | SubmissionErrorType::NetworkError
+                | SubmissionErrorType::InjectionFailed
         )
     }
 }
@@ -94,6 +99,45 @@ impl std::fmt::Display for CommandError {
 
 impl std::error::Error for CommandError {}
 
+/// Real-time submission-lifecycle event broadcast as a prompt fans out across
+/// providers, so the frontend can render live per-provider progress instead of
+/// waiting for a final `SubmissionStatus`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type")]
+pub enum SubmissionEvent {
+    /// Emitted once per `submit_prompt` call, before any provider starts
+    Plan {
+        submission_id: String,
+        providers: Vec<ProviderId>,
+    },
+    /// A provider's submission transitioned to `InProgress`
+    Started {
+        submission_id: String,
+        provider_id: ProviderId,
+    },
+    /// A provider's submission transitioned to `Retrying`
+    Retrying {
+        submission_id: String,
+        provider_id: ProviderId,
+        attempt: u8,
+    },
+    /// A provider's submission reached a terminal state (`Success` or `Failed`)
+    Finished {
+        submission_id: String,
+        provider_id: ProviderId,
+        status: SubmissionStatus,
+        duration_ms: u64,
+        error_type: Option<SubmissionErrorType>,
+    },
+    /// A submission's full state changed (created, started, retrying,
+    /// succeeded, failed, or a harvested response arrived). Carries the
+    /// whole `Submission` so the frontend can replace its local copy instead
+    /// of polling `get_submission_status`.
+    Updated {
+        submission: crate::status::Submission,
+    },
+}
+
 /// Event payload for executing a prompt injection in a provider webview
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExecutePromptPayload {
@@ -103,6 +147,21 @@ pub struct ExecutePromptPayload {
     pub provider_id: ProviderId,
     /// JavaScript code to execute
     pub script: String,
+    /// Capability this script is allowed to run under; `Injector` rejects
+    /// dispatch if it doesn't match `provider_id`'s origin and operations
+    pub capability: crate::injection::capability::InjectionCapability,
+}
+
+/// Event payload for reporting a harvested provider response from the
+/// response-harvesting script, once the provider's generation finishes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportResponsePayload {
+    /// Submission this harvested response belongs to
+    pub submission_id: String,
+    /// Provider the response was harvested from
+    pub provider_id: ProviderId,
+    /// The captured response text
+    pub response_text: String,
 }
 
 /// Event payload for reporting prompt execution results from frontend
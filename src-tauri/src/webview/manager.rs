@@ -1,170 +1,749 @@
 // WebviewManager for creating and managing provider webviews
 
+use crate::injection::InjectionResult;
+use crate::layout::calculator::to_pixel_bounds;
+use crate::layout::LayoutConfiguration;
+use crate::providers::manager::ProviderManager;
+use crate::providers::{Observer, Provider, ProviderEvent};
 use crate::types::ProviderId;
-use crate::{log_error, log_info};
-use std::collections::HashMap;
-use std::sync::Mutex;
-use tauri::{AppHandle, WebviewUrl, WebviewWindow, WebviewWindowBuilder};
+use crate::{log_error, log_info, log_warn};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, Position, Rect, Size};
+use tokio::sync::oneshot;
+
+/// How long `execute_script` waits for the page to call back with a result
+/// before giving up on a call-id
+const SCRIPT_RESULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How often `spawn_auth_polling_loop` wakes to check which selected
+/// providers are due for a re-check
+const AUTH_POLL_SCAN_INTERVAL: Duration = Duration::from_secs(1);
+/// Starting re-check interval for a provider that was just (re)selected or
+/// just transitioned between authenticated/`requires_login`
+const AUTH_POLL_BASE_INTERVAL: Duration = Duration::from_secs(5);
+/// Upper bound the re-check interval backs off to for an already-stable,
+/// consecutively-authenticated provider
+const AUTH_POLL_MAX_INTERVAL: Duration = Duration::from_secs(320);
+
+/// Per-provider auth polling bookkeeping: when it's next due, how far its
+/// interval has backed off, and the last observed result (so a
+/// `requires_login`/authenticated transition can be detected)
+struct AuthPollState {
+    next_due: Instant,
+    interval: Duration,
+    last_checked: Option<Instant>,
+    last_authenticated: Option<bool>,
+}
+
+impl AuthPollState {
+    fn due_now() -> Self {
+        Self {
+            next_due: Instant::now(),
+            interval: AUTH_POLL_BASE_INTERVAL,
+            last_checked: None,
+            last_authenticated: None,
+        }
+    }
+}
 
 /// Manages webviews for LLM provider interfaces
 pub struct WebviewManager {
-    webviews: Mutex<HashMap<ProviderId, WebviewWindow>>,
+    /// Senders for script executions awaiting their result, keyed by the
+    /// call-id generated for that execution
+    pending_scripts: Mutex<HashMap<String, oneshot::Sender<InjectionResult>>>,
+    /// Subscribers for `AuthStatusChanged`, the `WebviewManager`-side
+    /// counterpart of `ProviderManager`'s observer list
+    auth_observers: Mutex<Vec<Weak<dyn Observer>>>,
+    /// Per-provider auth-polling schedule, keyed by `ProviderId`; consulted
+    /// and advanced by `evaluate_auth_polling`
+    auth_poll_state: Mutex<HashMap<ProviderId, AuthPollState>>,
+    /// Aggregates `ResponseChunk`s posted back by the document-start capture
+    /// scripts `commands::sync_provider_webview` registers as init scripts on
+    /// each provider's child webview
+    response_collector: super::response_collector::ResponseCollector,
 }
 
 impl WebviewManager {
     /// Creates a new WebviewManager
     pub fn new() -> Self {
         Self {
-            webviews: Mutex::new(HashMap::new()),
+            pending_scripts: Mutex::new(HashMap::new()),
+            auth_observers: Mutex::new(Vec::new()),
+            auth_poll_state: Mutex::new(HashMap::new()),
+            response_collector: super::response_collector::ResponseCollector::new(),
+        }
+    }
+
+    /// Subscribes to the live stream of `ResponseChunk`s ingested via
+    /// `ingest_response_chunk`
+    pub fn subscribe_response_chunks(&self) -> tokio::sync::broadcast::Receiver<super::ResponseChunk> {
+        self.response_collector.subscribe()
+    }
+
+    /// Records a `ResponseChunk` reported by a provider's streaming capture
+    /// script, returning that provider's aggregated reply text so far
+    /// (including this chunk)
+    pub fn ingest_response_chunk(&self, chunk: super::ResponseChunk) -> String {
+        self.response_collector.ingest(chunk)
+    }
+
+    /// Registers `observer` to receive `AuthStatusChanged` events. The
+    /// manager only holds a `Weak` reference, so letting the caller's `Arc`
+    /// drop is enough to unsubscribe.
+    pub fn subscribe(&self, observer: &Arc<dyn Observer>) {
+        self.auth_observers.lock().unwrap().push(Arc::downgrade(observer));
+    }
+
+    /// Removes `observer` from the subscriber list, if still present.
+    pub fn unsubscribe(&self, observer: &Arc<dyn Observer>) {
+        let target = Arc::as_ptr(observer);
+        self.auth_observers
+            .lock()
+            .unwrap()
+            .retain(|weak| !matches!(weak.upgrade(), Some(o) if Arc::as_ptr(&o) == target));
+    }
+
+    /// Fans an `AuthStatusChanged` event out to every still-live observer,
+    /// pruning any whose `Arc` has since been dropped. Called once an auth
+    /// check has actually transitioned a provider's authenticated state.
+    pub fn notify_auth_status_changed(&self, provider_id: ProviderId, is_authenticated: bool) {
+        let mut observers = self.auth_observers.lock().unwrap();
+        observers.retain(|weak| {
+            if let Some(observer) = weak.upgrade() {
+                observer.notify(ProviderEvent::AuthStatusChanged {
+                    provider_id,
+                    is_authenticated,
+                });
+                true
+            } else {
+                false
+            }
+        });
+    }
+
+    /// Shared evaluation step for the auth-polling scheduler: a single call
+    /// covering every currently-selected provider, rather than one scheduled
+    /// task per webview. For each provider whose `next_due` has elapsed,
+    /// re-runs its `generate_auth_check_script` and advances its backoff:
+    ///
+    /// - a `requires_login`/authenticated transition resets the interval to
+    ///   `AUTH_POLL_BASE_INTERVAL` and fires `AuthStatusChanged`
+    /// - a repeat "still authenticated" result doubles the interval, capped
+    ///   at `AUTH_POLL_MAX_INTERVAL`
+    ///
+    /// Providers absent from `selected_providers` (deselected, or never
+    /// selected) have their schedule entry dropped, so selecting one again
+    /// later starts fresh at the minimum interval rather than resuming a
+    /// stale backoff.
+    ///
+    /// `app` is forwarded to `execute_script` so the check actually resolves
+    /// a live `"{provider}-webview"` child webview rather than the dead
+    /// top-level registry this used to consult.
+    pub async fn evaluate_auth_polling(&self, app: &AppHandle, selected_providers: &[Provider]) {
+        let selected_ids: HashSet<ProviderId> = selected_providers.iter().map(|p| p.id).collect();
+        self.auth_poll_state
+            .lock()
+            .unwrap()
+            .retain(|id, _| selected_ids.contains(id));
+
+        for provider in selected_providers {
+            let due = {
+                let mut state = self.auth_poll_state.lock().unwrap();
+                state.entry(provider.id).or_insert_with(AuthPollState::due_now).next_due
+            };
+
+            if Instant::now() < due {
+                continue;
+            }
+
+            let _span = crate::logging::start_span("auth_status_check");
+            let check_started = Instant::now();
+            let script = self.generate_auth_check_script(provider);
+            let check_result = self.execute_script(app, provider.id, &script).await;
+            crate::logging::record_duration_metric(
+                "auth_check_duration_ms",
+                check_started.elapsed().as_millis() as u64,
+            );
+
+            let is_authenticated = match check_result {
+                Ok(result) => result
+                    .data
+                    .as_ref()
+                    .and_then(|data| data.get("authenticated"))
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false),
+                Err(e) => {
+                    log_warn!("Auth check failed; leaving schedule and auth status unchanged", {
+                        "provider_id": format!("{:?}", provider.id),
+                        "error": e
+                    });
+                    continue;
+                }
+            };
+
+            let transitioned = {
+                let mut state = self.auth_poll_state.lock().unwrap();
+                let entry = state.entry(provider.id).or_insert_with(AuthPollState::due_now);
+                let transitioned = entry.last_authenticated != Some(is_authenticated);
+
+                entry.last_checked = Some(Instant::now());
+                entry.last_authenticated = Some(is_authenticated);
+                entry.interval = if transitioned {
+                    AUTH_POLL_BASE_INTERVAL
+                } else if is_authenticated {
+                    (entry.interval * 2).min(AUTH_POLL_MAX_INTERVAL)
+                } else {
+                    entry.interval
+                };
+                entry.next_due = Instant::now() + entry.interval;
+
+                transitioned
+            };
+
+            if transitioned {
+                log_info!("Provider auth status transitioned", {
+                    "provider_id": format!("{:?}", provider.id),
+                    "is_authenticated": is_authenticated
+                });
+                self.notify_auth_status_changed(provider.id, is_authenticated);
+            }
         }
     }
 
-    /// Gets or creates a webview for the specified provider
+    /// Re-applies a `LayoutConfiguration` to every live provider child
+    /// webview in one pass, converting each `PanelDimension`'s fractional
+    /// `(x, y, w, h)` into physical pixel bounds for the given parent size
+    /// and calling `set_bounds` on the matching `"{provider}-webview"` child.
     ///
-    /// This method ensures that only one webview exists per provider.
-    /// If a webview already exists, it returns Ok(())
-    pub fn get_or_create_webview(
+    /// Child webviews do not follow their parent window automatically, so
+    /// this is meant to be re-run whenever the host container resizes or
+    /// scrolls, not just once at creation time, the same way `sync_layout`
+    /// already re-anchors panels on a viewport change.
+    pub fn apply_layout(
         &self,
         app: &AppHandle,
-        provider_id: ProviderId,
-        url: &str,
-        name: &str,
+        layout: &LayoutConfiguration,
+        parent_width: f64,
+        parent_height: f64,
     ) -> Result<(), String> {
-        let mut webviews = self
-            .webviews
-            .lock()
-            .map_err(|e| format!("Failed to acquire webview lock: {}", e))?;
+        let panels = to_pixel_bounds(layout, parent_width, parent_height);
 
-        // Check if webview already exists
-        if webviews.contains_key(&provider_id) {
-            log_info!("Webview already exists", {
-                "provider_id": format!("{:?}", provider_id)
-            });
-            return Ok(());
-        }
+        for panel in &panels {
+            let label = format!("{}-webview", panel.provider_id.as_str().to_lowercase());
 
-        log_info!("Creating new webview", {
-            "provider_id": format!("{:?}", provider_id),
-            "url": url
-        });
+            let Some(webview) = app.get_webview(&label) else {
+                log_info!("No live child webview to re-anchor", { "label": &label });
+                continue;
+            };
 
-        // Create a new webview window
-        let label = format!("provider-{:?}", provider_id).to_lowercase();
-        let webview = WebviewWindowBuilder::new(app, &label, WebviewUrl::External(url.parse().unwrap()))
-            .title(name)
-            .visible(true)
-            .build()
-            .map_err(|e| {
-                log_error!("Failed to create webview", {
-                    "provider_id": format!("{:?}", provider_id),
-                    "error": e.to_string()
-                });
-                format!("Failed to create webview: {}", e)
+            let bounds = Rect {
+                position: Position::Logical(tauri::LogicalPosition { x: panel.x, y: panel.y }),
+                size: Size::Logical(tauri::LogicalSize {
+                    width: panel.width,
+                    height: panel.height,
+                }),
+            };
+
+            webview.set_bounds(bounds).map_err(|e| {
+                format!("Failed to set bounds for {}: {}", label, e)
             })?;
+        }
 
-        log_info!("Webview created successfully", {
-            "provider_id": format!("{:?}", provider_id),
-            "label": &label
+        log_info!("Applied layout to child webviews", {
+            "panel_count": panels.len(),
+            "parent_size": format!("{}x{}", parent_width, parent_height)
         });
 
-        // Store the webview
-        webviews.insert(provider_id, webview);
-
         Ok(())
     }
 
-    /// Executes JavaScript in a provider's webview
+    /// Captures a PNG snapshot of a provider's live child webview (the
+    /// `"{provider}-webview"` created by `sync_provider_webview`), the same
+    /// technique WebDriver test harnesses use to capture state at the moment
+    /// of a failure. Used by `bridge_failures_to_screenshots` to save a
+    /// diagnostic image whenever a submission fails.
+    ///
+    /// Only implemented on Linux today, via WebKitGTK's snapshot API — the
+    /// platform this crate is developed and tested against (see the user
+    /// agent fix in `sync_provider_webview`). Other platforms return an
+    /// error until their native capture path is wired up.
+    pub fn capture_screenshot(&self, app: &AppHandle, provider_id: ProviderId) -> Result<Vec<u8>, String> {
+        let label = format!("{}-webview", provider_id.as_str().to_lowercase());
+        let webview = app
+            .get_webview(&label)
+            .ok_or_else(|| format!("No live webview to screenshot: {}", label))?;
+
+        #[cfg(target_os = "linux")]
+        {
+            capture_linux_snapshot(&webview)
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = webview;
+            Err(format!(
+                "Screenshot capture is not yet implemented for this platform (provider {:?})",
+                provider_id
+            ))
+        }
+    }
+
+    /// Builds a read-only probe script that reports whether `provider` is
+    /// currently logged in, by checking each of its registry
+    /// `auth_selectors` in order and returning `authenticated: true` as soon
+    /// as one matches an element on the page. Mirrors the non-mutating
+    /// style of `diagnostics::script_builder`'s probe scripts.
+    pub fn generate_auth_check_script(&self, provider: &crate::providers::Provider) -> String {
+        let selectors = provider
+            .auth_selectors
+            .iter()
+            .map(|s| format!(r#""{}""#, s.replace('"', r#"\""#)))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!(
+            r#"
+(function() {{
+    const selectors = [{selectors}];
+    for (const selector of selectors) {{
+        try {{
+            if (document.querySelector(selector)) {{
+                return {{ authenticated: true, matched_selector: selector }};
+            }}
+        }} catch (error) {{
+            // Treat an invalid selector as a non-match and keep checking.
+        }}
+    }}
+    return {{ authenticated: false, matched_selector: null }};
+}})();
+"#,
+            selectors = selectors,
+        )
+    }
+
+    /// Executes JavaScript in a provider's webview and waits for its real
+    /// result, round-tripped back via `report_script_result` once the page
+    /// calls `window.__CHENCHEN_IPC__` with a `ScriptMessage::Completed`.
+    ///
+    /// Looks the webview up by the same `"{provider}-webview"` label
+    /// `apply_layout`/`capture_screenshot` already resolve through `app`,
+    /// since that's the only registry a real provider webview is ever
+    /// created in (`commands::sync_provider_webview`'s child webview, not a
+    /// top-level window this manager tracks itself).
+    ///
+    /// A fresh call-id is generated per invocation so results can't cross
+    /// streams between concurrent calls, and the wait times out after
+    /// `SCRIPT_RESULT_TIMEOUT` in case the page never calls back (e.g. the
+    /// provider's DOM changed underneath the injected script).
     pub async fn execute_script(
         &self,
+        app: &AppHandle,
         provider_id: ProviderId,
         script: &str,
-    ) -> Result<String, String> {
-        let webviews = self
-            .webviews
-            .lock()
-            .map_err(|e| format!("Failed to acquire webview lock: {}", e))?;
+    ) -> Result<InjectionResult, String> {
+        let _span = crate::logging::start_span("webview_execute_script");
+        let call_id = uuid::Uuid::new_v4().to_string();
+        let (sender, receiver) = oneshot::channel();
+
+        {
+            let mut pending = self
+                .pending_scripts
+                .lock()
+                .map_err(|e| format!("Failed to acquire pending-scripts lock: {}", e))?;
+            pending.insert(call_id.clone(), sender);
+        }
 
-        let webview = webviews
-            .get(&provider_id)
-            .ok_or_else(|| format!("No webview found for provider {:?}", provider_id))?;
+        let label = format!("{}-webview", provider_id.as_str().to_lowercase());
+        let eval_result = {
+            let webview = app
+                .get_webview(&label)
+                .ok_or_else(|| format!("No webview found for provider {:?}", provider_id))?;
 
-        log_info!("Executing script in webview", {
-            "provider_id": format!("{:?}", provider_id),
-            "script_length": script.len()
-        });
+            log_info!("Executing script in webview", {
+                "provider_id": format!("{:?}", provider_id),
+                "call_id": &call_id,
+                "script_length": script.len()
+            });
+
+            webview.eval(&wrap_script_with_callback(&call_id, script))
+        };
+
+        if let Err(e) = eval_result {
+            if let Ok(mut pending) = self.pending_scripts.lock() {
+                pending.remove(&call_id);
+            }
+            log_error!("Script execution failed", {
+                "provider_id": format!("{:?}", provider_id),
+                "call_id": &call_id,
+                "error": e.to_string()
+            });
+            return Err(format!("Failed to execute script: {}", e));
+        }
 
-        // Execute the script
-        // Note: eval() doesn't return a value in Tauri 2.0, we need to use a different approach
-        // For now, we'll use eval_async or modify the script to communicate via events
-        webview
-            .eval(script)
-            .map_err(|e| {
-                log_error!("Script execution failed", {
+        match tokio::time::timeout(SCRIPT_RESULT_TIMEOUT, receiver).await {
+            Ok(Ok(result)) => {
+                log_info!("Script execution completed", {
                     "provider_id": format!("{:?}", provider_id),
-                    "error": e.to_string()
+                    "call_id": &call_id,
+                    "success": result.success
                 });
-                format!("Failed to execute script: {}", e)
-            })?;
+                Ok(result)
+            }
+            Ok(Err(_)) => Err(format!(
+                "Script result channel for call_id {} was dropped before completion",
+                call_id
+            )),
+            Err(_) => {
+                if let Ok(mut pending) = self.pending_scripts.lock() {
+                    pending.remove(&call_id);
+                }
+                Err(format!(
+                    "Script execution timed out after {:?} waiting for call_id {}",
+                    SCRIPT_RESULT_TIMEOUT, call_id
+                ))
+            }
+        }
+    }
 
-        log_info!("Script execution initiated", {
-            "provider_id": format!("{:?}", provider_id)
-        });
+    /// Verifies a provider's recorded DOM `contract` against its live page:
+    /// injects a generated script checking every expectation, parses the
+    /// per-expectation results back into a `VerificationReport`, and emits
+    /// the report through the `StructuredLog` pipeline (a warning naming the
+    /// drifted expectations if any failed, otherwise an info line) so a
+    /// scheduled run surfaces exactly what a provider UI upgrade broke.
+    pub async fn verify_contract(
+        &self,
+        app: &AppHandle,
+        provider_id: ProviderId,
+        contract: &crate::contract::ProviderContract,
+    ) -> Result<crate::contract::VerificationReport, String> {
+        let script = crate::contract::script_builder::generate_contract_script(&contract.expectations);
+        let injection_result = self.execute_script(app, provider_id, &script).await?;
 
-        // For now, return a default success result
-        // In a real implementation, we'd use Tauri events to get the actual result
-        Ok(r#"{"success":true,"error_message":null,"element_found":true,"submit_triggered":true}"#.to_string())
+        let payload = injection_result
+            .data
+            .ok_or_else(|| "Contract script returned no structured data".to_string())?;
+        let results: Vec<crate::contract::ExpectationResult> = serde_json::from_value(
+            payload
+                .get("results")
+                .cloned()
+                .ok_or_else(|| "Contract script result missing 'results'".to_string())?,
+        )
+        .map_err(|e| format!("Failed to parse contract verification results: {}", e))?;
+
+        let report = crate::contract::VerificationReport {
+            provider_id,
+            checked_at: chrono::Utc::now().to_rfc3339(),
+            results,
+        };
+
+        if report.passed() {
+            log_info!("Contract verification passed", {
+                "provider_id": format!("{:?}", provider_id),
+                "checked_at": &report.checked_at
+            });
+        } else {
+            log_warn!("Contract verification found drifted expectations", {
+                "provider_id": format!("{:?}", provider_id),
+                "checked_at": &report.checked_at,
+                "failed_expectations": report.failed_expectation_names()
+            });
+        }
+
+        Ok(report)
     }
 
-    /// Closes a provider's webview
-    pub fn close_webview(&self, provider_id: ProviderId) -> Result<(), String> {
-        let mut webviews = self
-            .webviews
+    /// Fulfills a pending `execute_script` call with its result, called from
+    /// the `report_script_result` command once the injected script's
+    /// `window.__CHENCHEN_IPC__` callback fires with a `Completed` message
+    pub fn resolve_script_result(
+        &self,
+        call_id: &str,
+        result: InjectionResult,
+    ) -> Result<(), String> {
+        let mut pending = self
+            .pending_scripts
             .lock()
-            .map_err(|e| format!("Failed to acquire webview lock: {}", e))?;
+            .map_err(|e| format!("Failed to acquire pending-scripts lock: {}", e))?;
 
-        if let Some(webview) = webviews.remove(&provider_id) {
-            log_info!("Closing webview", {
-                "provider_id": format!("{:?}", provider_id)
-            });
+        match pending.remove(call_id) {
+            Some(sender) => {
+                let _ = sender.send(result);
+                Ok(())
+            }
+            None => Err(format!("No pending script execution for call_id {}", call_id)),
+        }
+    }
 
-            webview.close().map_err(|e| {
-                log_error!("Failed to close webview", {
-                    "provider_id": format!("{:?}", provider_id),
+}
+
+impl Default for WebviewManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Wraps `script` so it posts its result back to Rust via
+/// `window.__CHENCHEN_IPC__`, tagged with `call_id` so `report_script_result`
+/// can route it to the right pending `execute_script` call. `__CHENCHEN_IPC__`
+/// is expected to forward to `window.__TAURI__.core.invoke('report_script_result', ...)`
+/// from an init script (not yet wired end-to-end — see T-ROUNDTRIP).
+fn wrap_script_with_callback(call_id: &str, script: &str) -> String {
+    format!(
+        r#"
+(function() {{
+    function post(message) {{
+        window.__CHENCHEN_IPC__(JSON.stringify(message));
+    }}
+
+    post({{ type: "Started", call_id: "{call_id}" }});
+
+    try {{
+        const result = (function() {{ {script} }})();
+        post({{ type: "Completed", call_id: "{call_id}", result: result }});
+    }} catch (error) {{
+        post({{
+            type: "Completed",
+            call_id: "{call_id}",
+            result: {{
+                success: false,
+                error_message: String(error),
+                element_found: false,
+                submit_triggered: false
+            }}
+        }});
+    }}
+}})();
+"#,
+        call_id = call_id,
+        script = script,
+    )
+}
+
+/// Renders a GTK/WebKit snapshot of `webview`'s current page to a PNG byte
+/// buffer. `WebKitWebView::snapshot` is itself callback-based, so the result
+/// is bridged back to this synchronous call via a channel; the platform
+/// webview callback fires on the same GTK main loop this is normally called
+/// from off of (e.g. the retry scheduler's async task), so this blocks the
+/// calling thread rather than the UI thread.
+#[cfg(target_os = "linux")]
+fn capture_linux_snapshot(webview: &tauri::Webview) -> Result<Vec<u8>, String> {
+    use std::sync::mpsc;
+    use webkit2gtk::WebViewExt;
+
+    let (tx, rx) = mpsc::channel();
+
+    webview
+        .with_webview(move |platform_webview| {
+            let tx = tx.clone();
+            platform_webview.inner().snapshot(
+                webkit2gtk::SnapshotRegion::FullDocument,
+                webkit2gtk::SnapshotOptions::NONE,
+                None::<&gio::Cancellable>,
+                move |result| {
+                    let png = result.ok().and_then(|surface| {
+                        let mut buf = Vec::new();
+                        surface.write_to_png(&mut buf).ok()?;
+                        Some(buf)
+                    });
+                    let _ = tx.send(png);
+                },
+            );
+        })
+        .map_err(|e| format!("Failed to access platform webview: {}", e))?;
+
+    rx.recv_timeout(Duration::from_secs(5))
+        .map_err(|e| format!("Timed out waiting for webview snapshot: {}", e))?
+        .ok_or_else(|| "Failed to encode webview snapshot as PNG".to_string())
+}
+
+/// Returns the app-data path a failure screenshot for `submission_id`
+/// should be written to, creating its parent directory if needed
+fn failure_screenshot_path(submission_id: &str) -> std::path::PathBuf {
+    dirs::data_dir()
+        .map(|dir| dir.join("chenchen").join("screenshots").join(format!("{}.png", submission_id)))
+        .unwrap_or_default()
+}
+
+/// Subscribes to a `StatusTracker`'s event stream and, whenever a submission
+/// finishes in the `Failed` state, captures a screenshot of the offending
+/// provider's webview and records where it was saved — the same technique
+/// WebDriver test harnesses use to capture state at the moment of failure,
+/// invaluable when a provider silently changes its DOM and `Injector`'s
+/// selectors stop matching.
+pub fn bridge_failures_to_screenshots(
+    tracker: std::sync::Arc<crate::status::tracker::StatusTracker>,
+    webview_manager: std::sync::Arc<WebviewManager>,
+    app: AppHandle,
+) {
+    use crate::types::{SubmissionEvent, SubmissionStatus};
+
+    tauri::async_runtime::spawn(async move {
+        let mut events = tracker.subscribe();
+
+        while let Ok(event) = events.recv().await {
+            let SubmissionEvent::Finished {
+                submission_id,
+                provider_id,
+                status: SubmissionStatus::Failed,
+                ..
+            } = event
+            else {
+                continue;
+            };
+
+            let png = match webview_manager.capture_screenshot(&app, provider_id) {
+                Ok(png) => png,
+                Err(e) => {
+                    log_error!("Failed to capture failure screenshot", {
+                        "submission_id": &submission_id,
+                        "provider_id": format!("{:?}", provider_id),
+                        "error": e
+                    });
+                    continue;
+                }
+            };
+
+            let path = failure_screenshot_path(&submission_id);
+            if let Some(parent) = path.parent() {
+                let _ = std::fs::create_dir_all(parent);
+            }
+
+            if let Err(e) = std::fs::write(&path, png) {
+                log_error!("Failed to write failure screenshot", {
+                    "submission_id": &submission_id,
+                    "path": format!("{:?}", path),
                     "error": e.to_string()
                 });
-                format!("Failed to close webview: {}", e)
-            })?;
+                continue;
+            }
+
+            if let Err(e) = tracker.record_failure_screenshot(&submission_id, path) {
+                log_error!("Failed to record failure screenshot path", {
+                    "submission_id": &submission_id,
+                    "error": e.to_string()
+                });
+            }
         }
+    });
+}
 
-        Ok(())
+/// Spawns the shared auth-polling task: a single background loop (never one
+/// per webview) that wakes every `AUTH_POLL_SCAN_INTERVAL`, reads the
+/// currently selected providers from `provider_manager`, and hands them to
+/// `WebviewManager::evaluate_auth_polling`, which skips anything not due yet.
+///
+/// `app` is forwarded into each `evaluate_auth_polling` call so it can
+/// resolve the real `"{provider}-webview"` child webviews rather than a
+/// registry this manager never had anything registered in.
+pub fn spawn_auth_polling_loop(
+    app: AppHandle,
+    webview_manager: Arc<WebviewManager>,
+    provider_manager: Arc<Mutex<ProviderManager>>,
+) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(AUTH_POLL_SCAN_INTERVAL).await;
+
+            let selected: Vec<Provider> = {
+                let manager = provider_manager.lock().unwrap();
+                manager.get_selected_providers().into_iter().cloned().collect()
+            };
+
+            if selected.is_empty() {
+                continue;
+            }
+
+            webview_manager.evaluate_auth_polling(&app, &selected).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex as StdMutex;
+
+    struct RecordingObserver {
+        events: StdMutex<Vec<ProviderEvent>>,
     }
 
-    /// Closes all webviews
-    pub fn close_all(&self) -> Result<(), String> {
-        let mut webviews = self
-            .webviews
-            .lock()
-            .map_err(|e| format!("Failed to acquire webview lock: {}", e))?;
+    impl RecordingObserver {
+        fn new() -> Self {
+            Self {
+                events: StdMutex::new(Vec::new()),
+            }
+        }
+    }
 
-        log_info!("Closing all webviews", {
-            "count": webviews.len()
-        });
+    impl Observer for RecordingObserver {
+        fn notify(&self, event: ProviderEvent) {
+            self.events.lock().unwrap().push(event);
+        }
+    }
 
-        for (provider_id, webview) in webviews.drain() {
-            if let Err(e) = webview.close() {
-                log_error!("Failed to close webview", {
-                    "provider_id": format!("{:?}", provider_id),
-                    "error": e.to_string()
-                });
+    #[test]
+    fn test_notify_auth_status_changed_reaches_subscribed_observer() {
+        let manager = WebviewManager::new();
+        let recorder = Arc::new(RecordingObserver::new());
+        let observer: Arc<dyn Observer> = recorder.clone();
+        manager.subscribe(&observer);
+
+        manager.notify_auth_status_changed(ProviderId::Gemini, true);
+
+        let events = recorder.events.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            ProviderEvent::AuthStatusChanged {
+                provider_id,
+                is_authenticated,
+            } => {
+                assert_eq!(*provider_id, ProviderId::Gemini);
+                assert!(is_authenticated);
             }
+            other => panic!("Expected AuthStatusChanged, got {:?}", other),
         }
+    }
 
-        Ok(())
+    #[test]
+    fn test_unsubscribed_auth_observer_is_not_notified() {
+        let manager = WebviewManager::new();
+        let recorder = Arc::new(RecordingObserver::new());
+        let observer: Arc<dyn Observer> = recorder.clone();
+        manager.subscribe(&observer);
+        manager.unsubscribe(&observer);
+
+        manager.notify_auth_status_changed(ProviderId::Claude, false);
+
+        assert_eq!(recorder.events.lock().unwrap().len(), 0);
     }
-}
 
-impl Default for WebviewManager {
-    fn default() -> Self {
-        Self::new()
+    #[test]
+    fn test_generate_auth_check_script_checks_each_registry_selector() {
+        let manager = WebviewManager::new();
+        let provider = crate::providers::Provider::new(
+            ProviderId::ChatGPT,
+            vec!["nav[aria-label='Chat history']".to_string(), "button.profile".to_string()],
+        );
+
+        let script = manager.generate_auth_check_script(&provider);
+
+        assert!(script.contains("nav[aria-label='Chat history']"));
+        assert!(script.contains("button.profile"));
+        assert!(script.contains("querySelector"));
+        assert!(!script.contains(".click()"));
+    }
+
+    #[test]
+    fn test_wrap_script_with_callback_emits_started_and_completed() {
+        let wrapped = wrap_script_with_callback("call-1", "return 1;");
+
+        assert!(wrapped.contains("__CHENCHEN_IPC__"));
+        assert!(wrapped.contains(r#"type: "Started""#));
+        assert!(wrapped.contains(r#"type: "Completed""#));
+        assert!(wrapped.contains("call-1"));
+        assert!(wrapped.contains("return 1;"));
     }
 }
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use schemars::JsonSchema;
+use crate::injection::InjectionResult;
 use crate::types::ProviderId;
 
 /// Represents a webview session configuration for a provider
@@ -27,4 +28,29 @@ pub struct WebviewInfo {
     pub data_store_id: String,
 }
 
+/// Status protocol for an in-flight `WebviewManager::execute_script` call,
+/// modeled on Deno's test-runner status messages so the same call-id can
+/// report intermediate progress before its final result
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type")]
+pub enum ScriptMessage {
+    Started { call_id: String },
+    Progress { call_id: String, note: String },
+    Completed { call_id: String, result: InjectionResult },
+}
+
+/// An incremental piece of a provider's streamed response, posted back by the
+/// document-start capture script `Injector::prepare_init_script` generates.
+/// `delta` is the text observed since the last chunk for this provider, not
+/// the full response so far -- `response_collector::ResponseCollector`
+/// aggregates those into the running total. `done` marks the chunk that
+/// observed every completion selector disappear.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ResponseChunk {
+    pub provider_id: ProviderId,
+    pub delta: String,
+    pub done: bool,
+}
+
 pub mod manager;
+pub mod response_collector;
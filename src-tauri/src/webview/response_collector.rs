@@ -0,0 +1,146 @@
+// Aggregates streamed response chunks from document-start capture scripts
+// into full per-provider replies, while also fanning each chunk out live
+
+use super::ResponseChunk;
+use crate::types::ProviderId;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tokio::sync::broadcast;
+
+/// Capacity of the response-chunk broadcast channel. Slow or absent
+/// subscribers simply miss the oldest chunks rather than blocking senders,
+/// the same tradeoff `StatusTracker`'s event channel makes.
+const CHUNK_CHANNEL_CAPACITY: usize = 256;
+
+/// Collects `ResponseChunk`s as they arrive from a provider's streaming
+/// capture script, maintaining each provider's running total alongside a
+/// live broadcast stream -- mirrors `StatusTracker`'s split between durable
+/// state (`submissions`) and a broadcast of events about that state.
+pub struct ResponseCollector {
+    aggregated: Mutex<HashMap<ProviderId, String>>,
+    chunks: broadcast::Sender<ResponseChunk>,
+}
+
+impl ResponseCollector {
+    /// Creates a new, empty ResponseCollector
+    pub fn new() -> Self {
+        let (chunks, _) = broadcast::channel(CHUNK_CHANNEL_CAPACITY);
+        Self {
+            aggregated: Mutex::new(HashMap::new()),
+            chunks,
+        }
+    }
+
+    /// Subscribes to the live response-chunk stream
+    pub fn subscribe(&self) -> broadcast::Receiver<ResponseChunk> {
+        self.chunks.subscribe()
+    }
+
+    /// Appends `chunk`'s delta to its provider's running total, fans the
+    /// chunk out to every live subscriber, and returns the provider's
+    /// aggregated text so far (including this chunk). Once a `done` chunk
+    /// arrives, the running total is cleared so the next submission starts
+    /// from an empty string rather than continuing the previous reply.
+    pub fn ingest(&self, chunk: ResponseChunk) -> String {
+        let full_text = {
+            let mut aggregated = self.aggregated.lock().unwrap();
+            let entry = aggregated.entry(chunk.provider_id).or_default();
+            entry.push_str(&chunk.delta);
+            let full_text = entry.clone();
+
+            if chunk.done {
+                aggregated.remove(&chunk.provider_id);
+            }
+
+            full_text
+        };
+
+        // A send error just means no one is currently subscribed; the
+        // aggregated total above is still recorded regardless.
+        let _ = self.chunks.send(chunk);
+
+        full_text
+    }
+}
+
+impl Default for ResponseCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ProviderId;
+
+    #[test]
+    fn test_ingest_accumulates_deltas_for_the_same_provider() {
+        let collector = ResponseCollector::new();
+
+        collector.ingest(ResponseChunk {
+            provider_id: ProviderId::ChatGPT,
+            delta: "Hello".to_string(),
+            done: false,
+        });
+        let full_text = collector.ingest(ResponseChunk {
+            provider_id: ProviderId::ChatGPT,
+            delta: ", world".to_string(),
+            done: false,
+        });
+
+        assert_eq!(full_text, "Hello, world");
+    }
+
+    #[test]
+    fn test_ingest_keeps_providers_independent() {
+        let collector = ResponseCollector::new();
+
+        collector.ingest(ResponseChunk {
+            provider_id: ProviderId::ChatGPT,
+            delta: "from chatgpt".to_string(),
+            done: false,
+        });
+        let gemini_text = collector.ingest(ResponseChunk {
+            provider_id: ProviderId::Gemini,
+            delta: "from gemini".to_string(),
+            done: false,
+        });
+
+        assert_eq!(gemini_text, "from gemini");
+    }
+
+    #[test]
+    fn test_done_chunk_clears_the_running_total() {
+        let collector = ResponseCollector::new();
+
+        collector.ingest(ResponseChunk {
+            provider_id: ProviderId::ChatGPT,
+            delta: "final answer".to_string(),
+            done: true,
+        });
+        let restarted = collector.ingest(ResponseChunk {
+            provider_id: ProviderId::ChatGPT,
+            delta: "new reply".to_string(),
+            done: false,
+        });
+
+        assert_eq!(restarted, "new reply");
+    }
+
+    #[test]
+    fn test_subscriber_receives_ingested_chunks() {
+        let collector = ResponseCollector::new();
+        let mut receiver = collector.subscribe();
+
+        collector.ingest(ResponseChunk {
+            provider_id: ProviderId::Claude,
+            delta: "chunk".to_string(),
+            done: false,
+        });
+
+        let received = receiver.try_recv().expect("chunk should have been broadcast");
+        assert_eq!(received.provider_id, ProviderId::Claude);
+        assert_eq!(received.delta, "chunk");
+    }
+}